@@ -0,0 +1,194 @@
+//! Asynchronous variant of the BD18378 driver based on `embedded-hal-async`.
+
+use crate::channel::ChannelIndex;
+use crate::echo::EchoValidator;
+use crate::registers::{ReadRegister, WriteRegister};
+use crate::status::Status;
+use crate::{
+    check_init_status, Error, OperationResult, CHANNELS_PER_IC, CHANNELS_PER_REGISTER,
+    INIT_SEQUENCE, NOP_FRAME, STATUS_RESET_VALUE,
+};
+use embedded_hal_async::spi::{Error as _, SpiDevice};
+
+/// The `Bd18378Async` struct represents the ROHM BD18378 LED Driver IC accessed via
+/// an asynchronous SPI device.
+///
+/// It mirrors the blocking `Bd18378` driver, but awaits all SPI transfers instead of
+/// blocking the executor.
+pub struct Bd18378Async<'a, SPI: SpiDevice> {
+    spi: &'a mut SPI,
+    is_initialized: bool,
+    channel_enable: [bool; CHANNELS_PER_IC],
+}
+
+impl<'a, SPI: SpiDevice> Bd18378Async<'a, SPI> {
+    /// Creates a new instance of the `Bd18378Async` struct. It takes a mutable reference
+    /// to an asynchronous SPI device as an argument.
+    pub fn new(spi: &'a mut SPI) -> Self {
+        Bd18378Async {
+            spi,
+            is_initialized: false,
+            channel_enable: [false; CHANNELS_PER_IC],
+        }
+    }
+
+    /// Consumes the driver and returns the SPI device, e.g. to reuse the bus for another device.
+    ///
+    /// *Note: The IC keeps its current state.*
+    pub fn release(self) -> &'a mut SPI {
+        self.spi
+    }
+
+    /// Initializes the BD18378 LED Driver IC by writing a sequence of values to its registers.
+    /// The sequence is documented in the datasheet of the IC.
+    /// Afterwards the status flags are reset and the status register is read back. If the
+    /// power-on reset flag is still set, `Error::InitFailed` is returned.
+    /// Returns an `OperationResult` indicating success or failure of the initialization sequence.
+    pub async fn init(&mut self) -> OperationResult {
+        let mut echo = EchoValidator::new(1);
+        for (reg, value) in INIT_SEQUENCE.iter() {
+            let data = self.write_register(*reg, *value).await?;
+            // Validate the SPI transfer response by comparing it with the previous transaction's data.
+            echo.check([*reg as u8, *value], data)?;
+        }
+
+        self.write_register(WriteRegister::StatusReset, STATUS_RESET_VALUE)
+            .await?;
+
+        let status = self.read_register(ReadRegister::Status).await?;
+        check_init_status(Status::from_bits(status))?;
+
+        self.is_initialized = true;
+        Ok(())
+    }
+
+    /// Returns whether the BD18378 LED Driver IC is initialized.
+    ///
+    /// *Note: This is not a live view of the IC state, but rather a flag
+    /// indicating whether the initialization sequence has been successfully executed.*
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    /// Enable a single LED channel by its index.
+    ///
+    /// *Note: This function does not update the LED channel state immediately.
+    /// You need to call `update_all_channels()` to apply the changes.*
+    pub fn enable_channel(&mut self, ch: impl ChannelIndex) -> OperationResult {
+        self.set_channel_state(ch.index(), true)
+    }
+
+    /// Disable a single LED channel by its index.
+    ///
+    /// *Note: This function does not update the LED channel state immediately.
+    /// You need to call `update_all_channels()` to apply the changes.*
+    pub fn disable_channel(&mut self, ch: impl ChannelIndex) -> OperationResult {
+        self.set_channel_state(ch.index(), false)
+    }
+
+    /// Update all LED channels based on their enabled state.
+    ///
+    /// See `Bd18378::update_all_channels()` for the mapping of channels to register bits.
+    pub async fn update_all_channels(&mut self) -> OperationResult {
+        self.check_initialized()?;
+
+        let first_group_value = self.compute_channel_group_value(0);
+        self.write_register(WriteRegister::ChannelEnable00To05, first_group_value)
+            .await?;
+
+        let second_group_value = self.compute_channel_group_value(CHANNELS_PER_REGISTER);
+        self.write_register(WriteRegister::ChannelEnable06To11, second_group_value)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Set the calibration value for a specific LED channel.
+    ///
+    /// *Note: The calibration value is a 6-bit value, the upper 2 bits are ignored.
+    /// E.g. a value of 0x80 will result in a calibration value of 0x00. *
+    pub async fn set_channel_calibration(
+        &mut self,
+        ch: impl ChannelIndex,
+        calibration: u8,
+    ) -> OperationResult {
+        let ch = ch.index();
+        if ch >= CHANNELS_PER_IC {
+            return Err(Error::InvalidChannel);
+        }
+
+        self.check_initialized()?;
+
+        let register =
+            WriteRegister::try_from(WriteRegister::ChannelCalibration00 as u8 + ch as u8).unwrap();
+
+        self.write_register(register, calibration).await?;
+
+        Ok(())
+    }
+
+    /// Helper function to set the cached enabled state of a channel.
+    fn set_channel_state(&mut self, ch: usize, enabled: bool) -> OperationResult {
+        if ch >= CHANNELS_PER_IC {
+            return Err(Error::InvalidChannel);
+        }
+
+        self.check_initialized()?;
+
+        self.channel_enable[ch] = enabled;
+        Ok(())
+    }
+
+    /// Helper function to compute the value for the group of channels starting at `offset`.
+    fn compute_channel_group_value(&self, offset: usize) -> u8 {
+        let mut group_value = 0u8;
+        for ch in 0..CHANNELS_PER_REGISTER {
+            if self.channel_enable[offset + ch] {
+                group_value |= 1 << ch;
+            }
+        }
+        group_value
+    }
+
+    /// Writes a value to a specified register of the BD18378 LED Driver IC.
+    async fn write_register(&mut self, register: WriteRegister, value: u8) -> Result<[u8; 2], Error> {
+        let mut data = [register as u8, value];
+        let result = self.spi.transfer_in_place(&mut data).await;
+        match result {
+            Ok(()) => Ok(data),
+            Err(e) => Err(Error::SpiError(e.kind())),
+        }
+    }
+
+    /// Reads the value of a register of the BD18378 LED Driver IC.
+    ///
+    /// The value is shifted out during the frame following the read request, see
+    /// `Bd18378::read_register()`.
+    async fn read_register(&mut self, register: ReadRegister) -> Result<u8, Error> {
+        let request = [register as u8, 0x00u8];
+        let mut data = request;
+        if let Err(e) = self.spi.transfer_in_place(&mut data).await {
+            return Err(Error::SpiError(e.kind()));
+        }
+
+        let mut response = NOP_FRAME;
+        if let Err(e) = self.spi.transfer_in_place(&mut response).await {
+            return Err(Error::SpiError(e.kind()));
+        }
+        if response[0] != request[0] {
+            return Err(Error::CommunicationError {
+                expected: request,
+                received: response,
+            });
+        }
+        Ok(response[1])
+    }
+
+    /// Checks if the BD18378 LED Driver IC is initialized before performing any operation.
+    fn check_initialized(&self) -> OperationResult {
+        if !self.is_initialized {
+            return Err(Error::NotInitialized);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use crate::channel::ChannelIndex;
+use crate::{Bd18378, Error, CHANNELS_PER_IC};
+use embedded_hal::spi::SpiDevice;
+
+/// The `Bd18378Builder` struct accumulates the desired channel states and calibration
+/// values of the BD18378 LED Driver IC and applies them right after initialization.
+///
+/// Invalid channel indices are recorded and reported by `build()`, so the setup can be
+/// chained fluently:
+///
+/// ```
+/// # use bd18378::builder::Bd18378Builder;
+/// # use bd18378::dry_run::DryRunSpi;
+/// # let mut spi = DryRunSpi::<32>::new();
+/// let driver = Bd18378Builder::new(&mut spi)
+///     .enable(0)
+///     .enable(4)
+///     .calibrate(0, 0x10)
+///     .build()?;
+/// # Ok::<(), bd18378::Error>(())
+/// ```
+pub struct Bd18378Builder<'a, SPI: SpiDevice> {
+    spi: &'a mut SPI,
+    channel_enable: [bool; CHANNELS_PER_IC],
+    calibration: [Option<u8>; CHANNELS_PER_IC],
+    error: Option<Error>,
+}
+
+impl<'a, SPI: SpiDevice> Bd18378Builder<'a, SPI> {
+    /// Creates a new builder for the IC connected to the given SPI device, with all
+    /// channels disabled and no calibration values.
+    pub fn new(spi: &'a mut SPI) -> Self {
+        Bd18378Builder {
+            spi,
+            channel_enable: [false; CHANNELS_PER_IC],
+            calibration: [None; CHANNELS_PER_IC],
+            error: None,
+        }
+    }
+
+    /// Marks a single LED channel to be enabled.
+    pub fn enable(mut self, ch: impl ChannelIndex) -> Self {
+        if let Some(ch) = self.check_channel(ch.index()) {
+            self.channel_enable[ch] = true;
+        }
+        self
+    }
+
+    /// Sets the calibration value to be written to a single LED channel.
+    ///
+    /// *Note: The calibration value is a 6-bit value, the upper 2 bits are ignored.*
+    pub fn calibrate(mut self, ch: impl ChannelIndex, calibration: u8) -> Self {
+        if let Some(ch) = self.check_channel(ch.index()) {
+            self.calibration[ch] = Some(calibration);
+        }
+        self
+    }
+
+    /// Initializes the IC, writes the recorded calibration values in ascending channel
+    /// order and applies the recorded channel states. Returns the ready driver.
+    ///
+    /// If an invalid channel index was passed to the builder, `Error::InvalidChannel` is
+    /// returned without accessing the IC.
+    pub fn build(self) -> Result<Bd18378<'a, SPI>, Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let mut driver = Bd18378::new(self.spi);
+        driver.init()?;
+        for (ch, calibration) in self.calibration.iter().enumerate() {
+            if let Some(calibration) = calibration {
+                driver.set_channel_calibration(ch, *calibration)?;
+            }
+        }
+        for (ch, enabled) in self.channel_enable.iter().enumerate() {
+            if *enabled {
+                driver.enable_channel(ch)?;
+            }
+        }
+        driver.update_all_channels()?;
+
+        Ok(driver)
+    }
+
+    /// Helper function to validate a channel index, recording the first invalid one.
+    fn check_channel(&mut self, ch: usize) -> Option<usize> {
+        if ch >= CHANNELS_PER_IC {
+            self.error.get_or_insert(Error::InvalidChannel);
+            return None;
+        }
+        Some(ch)
+    }
+}
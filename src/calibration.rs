@@ -0,0 +1,28 @@
+/// The `LedBin` struct represents the brightness bin an LED was sorted into by its
+/// manufacturer. The bin is identified by its index into a `CalibrationModel`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LedBin(pub u8);
+
+/// The `CalibrationModel` struct maps LED brightness bins to calibration codes of the
+/// BD18378 LED Driver IC.
+///
+/// The code at index `n` is used for all channels fitted with an LED of `LedBin(n)`.
+/// Brighter bins should be given lower codes than darker bins to equalize the brightness
+/// across mismatched LEDs.
+#[derive(Debug, Clone, Copy)]
+pub struct CalibrationModel<'a> {
+    codes: &'a [u8],
+}
+
+impl<'a> CalibrationModel<'a> {
+    /// Creates a new `CalibrationModel` from a table of calibration codes indexed by bin.
+    pub const fn new(codes: &'a [u8]) -> Self {
+        CalibrationModel { codes }
+    }
+
+    /// Returns the calibration code for the given bin or `None` if the bin is unknown
+    /// to this model.
+    pub fn code_for(&self, bin: LedBin) -> Option<u8> {
+        self.codes.get(bin.0 as usize).copied()
+    }
+}
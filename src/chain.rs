@@ -0,0 +1,221 @@
+//! Driver for multiple BD18378 LED Driver ICs daisy-chained on one SPI bus.
+
+use crate::channel::ChannelIndex;
+use crate::echo::EchoValidator;
+use crate::registers::{ReadRegister, WriteRegister};
+use crate::status::Status;
+use crate::{
+    check_init_status, Error, OperationResult, CHANNELS_PER_IC, CHANNELS_PER_REGISTER,
+    INIT_SEQUENCE, NOP_FRAME, STATUS_RESET_VALUE,
+};
+use embedded_hal::spi::{Error as _, SpiDevice};
+
+/// The `Bd18378Chain` struct represents `N` ROHM BD18378 LED Driver ICs connected in a
+/// cascade, sharing one chip select.
+///
+/// Every access shifts one 2-byte frame per device through the chain in a single SPI
+/// transaction. Device 0 is the device connected to the controller, so its frame is
+/// sent last. Channel indices span all devices, channel `ch` maps to channel `ch % 12`
+/// of device `ch / 12`.
+pub struct Bd18378Chain<'a, SPI: SpiDevice, const N: usize> {
+    spi: &'a mut SPI,
+    is_initialized: bool,
+    channel_enable: [[bool; CHANNELS_PER_IC]; N],
+}
+
+impl<'a, SPI: SpiDevice, const N: usize> Bd18378Chain<'a, SPI, N> {
+    /// The total number of LED channels of the chain.
+    pub const CHANNELS: usize = CHANNELS_PER_IC * N;
+
+    /// Creates a new instance of the `Bd18378Chain` struct. It takes a mutable reference
+    /// to an SPI device as an argument.
+    pub fn new(spi: &'a mut SPI) -> Self {
+        Bd18378Chain {
+            spi,
+            is_initialized: false,
+            channel_enable: [[false; CHANNELS_PER_IC]; N],
+        }
+    }
+
+    /// Consumes the driver and returns the SPI device, e.g. to reuse the bus for another device.
+    ///
+    /// *Note: The ICs keep their current state.*
+    pub fn release(self) -> &'a mut SPI {
+        self.spi
+    }
+
+    /// Initializes all BD18378 LED Driver ICs of the chain by writing the initialization
+    /// sequence to every device in lockstep.
+    ///
+    /// Each response is validated against the frames of the previous transaction, which
+    /// have been shifted through the chain. Afterwards the status flags of all devices are
+    /// reset and read back. If the power-on reset flag of any device is still set,
+    /// `Error::InitFailed` is returned.
+    pub fn init(&mut self) -> OperationResult {
+        let mut echoes = [EchoValidator::new(1); N];
+        for (reg, value) in INIT_SEQUENCE.iter() {
+            let frame = [*reg as u8, *value];
+            let mut frames = [frame; N];
+            self.transfer(&mut frames)?;
+            for (echo, response) in echoes.iter_mut().zip(frames.iter()) {
+                echo.check(frame, *response)?;
+            }
+        }
+
+        let mut frames = [[WriteRegister::StatusReset as u8, STATUS_RESET_VALUE]; N];
+        self.transfer(&mut frames)?;
+
+        for status in self.read_register(ReadRegister::Status)? {
+            check_init_status(Status::from_bits(status))?;
+        }
+
+        self.is_initialized = true;
+        Ok(())
+    }
+
+    /// Returns whether the BD18378 LED Driver ICs of the chain are initialized.
+    ///
+    /// *Note: This is not a live view of the IC state, but rather a flag
+    /// indicating whether the initialization sequence has been successfully executed.*
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    /// Returns whether a LED channel of the chain is enabled.
+    pub fn is_channel_enabled(&self, ch: impl ChannelIndex) -> Result<bool, Error> {
+        let (device, ch) = Self::locate(ch.index())?;
+        Ok(self.channel_enable[device][ch])
+    }
+
+    /// Enable a single LED channel of the chain by its index.
+    ///
+    /// *Note: This function does not update the LED channel state immediately.
+    /// You need to call `update_all_channels()` to apply the changes.*
+    pub fn enable_channel(&mut self, ch: impl ChannelIndex) -> OperationResult {
+        self.set_channel_state(ch.index(), true)
+    }
+
+    /// Disable a single LED channel of the chain by its index.
+    ///
+    /// *Note: This function does not update the LED channel state immediately.
+    /// You need to call `update_all_channels()` to apply the changes.*
+    pub fn disable_channel(&mut self, ch: impl ChannelIndex) -> OperationResult {
+        self.set_channel_state(ch.index(), false)
+    }
+
+    /// Update all LED channels of the chain based on their enabled state.
+    ///
+    /// Each channel enable register is written to all devices in one transaction.
+    /// See `Bd18378::update_all_channels()` for the mapping of channels to register bits.
+    pub fn update_all_channels(&mut self) -> OperationResult {
+        self.check_initialized()?;
+
+        for (register, offset) in [
+            (WriteRegister::ChannelEnable00To05, 0),
+            (WriteRegister::ChannelEnable06To11, CHANNELS_PER_REGISTER),
+        ] {
+            let mut frames = [[0x00u8, 0x00u8]; N];
+            for (device, frame) in frames.iter_mut().enumerate() {
+                *frame = [register as u8, self.compute_channel_group_value(device, offset)];
+            }
+            self.transfer(&mut frames)?;
+        }
+
+        Ok(())
+    }
+
+    /// Set the calibration value for a specific LED channel of the chain.
+    ///
+    /// Only the device owning the channel is written, all other devices receive a
+    /// status register read, which leaves them unchanged.
+    ///
+    /// *Note: The calibration value is a 6-bit value, the upper 2 bits are ignored.
+    /// E.g. a value of 0x80 will result in a calibration value of 0x00. *
+    pub fn set_channel_calibration(
+        &mut self,
+        ch: impl ChannelIndex,
+        calibration: u8,
+    ) -> OperationResult {
+        let (device, ch) = Self::locate(ch.index())?;
+
+        self.check_initialized()?;
+
+        let mut frames = [NOP_FRAME; N];
+        frames[device] = [WriteRegister::ChannelCalibration00 as u8 + ch as u8, calibration];
+        self.transfer(&mut frames)
+    }
+
+    /// Helper function to split a chain channel index into the device and its local channel.
+    fn locate(ch: usize) -> Result<(usize, usize), Error> {
+        if ch >= Self::CHANNELS {
+            return Err(Error::InvalidChannel);
+        }
+        Ok((ch / CHANNELS_PER_IC, ch % CHANNELS_PER_IC))
+    }
+
+    /// Helper function to set the cached enabled state of a channel.
+    fn set_channel_state(&mut self, ch: usize, enabled: bool) -> OperationResult {
+        let (device, ch) = Self::locate(ch)?;
+
+        self.check_initialized()?;
+
+        self.channel_enable[device][ch] = enabled;
+        Ok(())
+    }
+
+    /// Helper function to compute the value for the group of channels of a device
+    /// starting at `offset`.
+    fn compute_channel_group_value(&self, device: usize, offset: usize) -> u8 {
+        let mut group_value = 0u8;
+        for ch in 0..CHANNELS_PER_REGISTER {
+            if self.channel_enable[device][offset + ch] {
+                group_value |= 1 << ch;
+            }
+        }
+        group_value
+    }
+
+    /// Shifts one frame per device through the chain in a single transaction.
+    ///
+    /// `frames` is indexed by device and receives the responses in place.
+    fn transfer(&mut self, frames: &mut [[u8; 2]; N]) -> OperationResult {
+        // the frame of the last device has to be shifted in first
+        frames.reverse();
+        let result = self.spi.transfer_in_place(frames.as_flattened_mut());
+        frames.reverse();
+        result.map_err(|e| Error::SpiError(e.kind()))
+    }
+
+    /// Reads a register of all devices of the chain.
+    ///
+    /// Each device shifts out the register address and value during the transaction
+    /// following the read request (datasheet p.18). A status read is used as the
+    /// following transaction.
+    fn read_register(&mut self, register: ReadRegister) -> Result<[u8; N], Error> {
+        let request = [register as u8, 0x00u8];
+        let mut frames = [request; N];
+        self.transfer(&mut frames)?;
+
+        let mut frames = [NOP_FRAME; N];
+        self.transfer(&mut frames)?;
+        let mut values = [0x00u8; N];
+        for (value, frame) in values.iter_mut().zip(frames.iter()) {
+            if frame[0] != request[0] {
+                return Err(Error::CommunicationError {
+                    expected: request,
+                    received: *frame,
+                });
+            }
+            *value = frame[1];
+        }
+        Ok(values)
+    }
+
+    /// Checks if the BD18378 LED Driver ICs are initialized before performing any operation.
+    fn check_initialized(&self) -> OperationResult {
+        if !self.is_initialized {
+            return Err(Error::NotInitialized);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use crate::CHANNELS_PER_REGISTER;
+use core::ops::Range;
+
+/// The `Channel` enum represents the LED channels of the BD18378 LED Driver IC.
+///
+/// It can be used instead of a raw channel index in all channel related methods of the
+/// driver, ruling out invalid channel indices at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Channel {
+    Ch0 = 0,
+    Ch1 = 1,
+    Ch2 = 2,
+    Ch3 = 3,
+    Ch4 = 4,
+    Ch5 = 5,
+    Ch6 = 6,
+    Ch7 = 7,
+    Ch8 = 8,
+    Ch9 = 9,
+    Ch10 = 10,
+    Ch11 = 11,
+}
+
+impl From<Channel> for usize {
+    fn from(channel: Channel) -> Self {
+        channel as usize
+    }
+}
+
+/// The `ChannelIndex` trait is implemented by all types which can be used to address
+/// an LED channel, i.e. raw `usize` indices and the typed `Channel` enum.
+pub trait ChannelIndex {
+    /// Returns the raw index of the addressed channel.
+    fn index(self) -> usize;
+}
+
+impl ChannelIndex for usize {
+    fn index(self) -> usize {
+        self
+    }
+}
+
+impl ChannelIndex for Channel {
+    fn index(self) -> usize {
+        self.into()
+    }
+}
+
+/// The `ChannelGroup` enum represents the two groups of LED channels, each controlled by
+/// its own channel enable register of the BD18378 LED Driver IC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChannelGroup {
+    /// Channels 0 to 5, controlled by `WriteRegister::ChannelEnable00To05`.
+    Low,
+    /// Channels 6 to 11, controlled by `WriteRegister::ChannelEnable06To11`.
+    High,
+}
+
+impl ChannelGroup {
+    /// Returns the index of the first channel of the group.
+    pub const fn first_channel(self) -> usize {
+        match self {
+            ChannelGroup::Low => 0,
+            ChannelGroup::High => CHANNELS_PER_REGISTER,
+        }
+    }
+
+    /// Returns the range of channel indices belonging to the group, i.e. `0..6` for
+    /// `Low` and `6..12` for `High`.
+    ///
+    /// It is provided on `ChannelGroup` rather than on `Bd18378`, so it can be used
+    /// without a driver instance.
+    pub const fn channels(self) -> Range<usize> {
+        let start = self.first_channel();
+        start..start + CHANNELS_PER_REGISTER
+    }
+}
@@ -0,0 +1,12 @@
+use crate::CHANNELS_PER_IC;
+
+/// The `Config` struct describes the desired startup state of the BD18378 LED Driver IC,
+/// which is applied by `Bd18378::init_with_config()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    /// The enabled LED channels, bit 0 corresponds to channel 0, bit 11 to channel 11.
+    pub channel_mask: u16,
+    /// The calibration value of each LED channel, only the lower 6 bits are used.
+    pub calibration: [u8; CHANNELS_PER_IC],
+}
@@ -0,0 +1,94 @@
+//! A null SPI device to exercise the driver without hardware.
+
+use crate::registers::ReadRegister;
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiDevice};
+
+/// The `DryRunSpi` struct is an SPI device which does not access any hardware, but
+/// records the frames written to it.
+///
+/// It emulates the behavior of the BD18378 LED Driver IC closely enough for the driver to
+/// work, i.e. each frame is answered with the echo of the previous frame, and a register
+/// read with the register address and the value 0 during the following frame. This allows
+/// testing application logic against the driver without a real SPI device or a mock
+/// library:
+///
+/// ```
+/// use bd18378::Bd18378;
+/// use bd18378::dry_run::DryRunSpi;
+///
+/// let mut spi = DryRunSpi::<32>::new();
+/// let mut driver = Bd18378::new(&mut spi);
+/// driver.init().unwrap();
+/// driver.enable_channel(0).unwrap();
+/// driver.update_all_channels().unwrap();
+///
+/// assert_eq!(spi.recorded_writes().last(), Some(&[0x57, 0x00]));
+/// ```
+///
+/// Up to `N` written frames are recorded, further frames are answered but not recorded.
+#[derive(Debug)]
+pub struct DryRunSpi<const N: usize> {
+    writes: [[u8; 2]; N],
+    len: usize,
+    next_response: [u8; 2],
+}
+
+impl<const N: usize> DryRunSpi<N> {
+    /// Creates a new `DryRunSpi` without any recorded frames.
+    pub const fn new() -> Self {
+        DryRunSpi {
+            writes: [[0x00u8, 0x00u8]; N],
+            len: 0,
+            next_response: [0x00u8, 0x00u8],
+        }
+    }
+
+    /// Returns the recorded frames in the order they were written, each consisting of
+    /// the register address and the written value.
+    pub fn recorded_writes(&self) -> &[[u8; 2]] {
+        &self.writes[..self.len]
+    }
+
+    /// Discards all recorded frames.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Helper function to answer a single frame.
+    fn transfer_frame(&mut self, frame: &mut [u8]) {
+        let received = [frame[0], frame[1]];
+        frame.copy_from_slice(&self.next_response);
+        if ReadRegister::from_repr(received[0]).is_some() {
+            self.next_response = [received[0], 0x00];
+            return;
+        }
+        if self.len < N {
+            self.writes[self.len] = received;
+            self.len += 1;
+        }
+        self.next_response = received;
+    }
+}
+
+impl<const N: usize> Default for DryRunSpi<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> ErrorType for DryRunSpi<N> {
+    type Error = ErrorKind;
+}
+
+impl<const N: usize> SpiDevice for DryRunSpi<N> {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        for operation in operations.iter_mut() {
+            if let Operation::TransferInPlace(data) = operation {
+                for frame in data.chunks_exact_mut(2) {
+                    self.transfer_frame(frame);
+                }
+            }
+        }
+        Ok(())
+    }
+}
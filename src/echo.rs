@@ -0,0 +1,66 @@
+//! Validation of the responses echoed by the BD18378 LED Driver IC.
+
+use crate::{Error, OperationResult, MAX_ECHO_DEPTH};
+
+/// The number of sent frames kept to detect a restarted echo pipeline.
+const HISTORY_LEN: usize = 2 * MAX_ECHO_DEPTH;
+
+/// The `EchoValidator` struct checks the responses of a sequence of frames sent to a
+/// single device against the frames sent before.
+///
+/// The IC shifts out the frame it received `depth` transactions before, so the first
+/// `depth` responses are not checked. Once the echo pipeline is established, a response
+/// of all zeros or a response repeating a frame sent before the expected one means the
+/// device restarted its pipeline, i.e. it was reset in the middle of the sequence, and
+/// `Error::UnexpectedReset` is returned.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct EchoValidator {
+    sent: [[u8; 2]; HISTORY_LEN],
+    depth: usize,
+    count: usize,
+    established: bool,
+}
+
+impl EchoValidator {
+    /// Creates a new `EchoValidator` for an echo pipeline of the given depth.
+    ///
+    /// The depth has to be in the range from 1 to `MAX_ECHO_DEPTH`.
+    pub(crate) const fn new(depth: usize) -> Self {
+        EchoValidator {
+            sent: [[0x00u8, 0x00u8]; HISTORY_LEN],
+            depth,
+            count: 0,
+            established: false,
+        }
+    }
+
+    /// Checks the response received while sending `frame`.
+    pub(crate) fn check(&mut self, frame: [u8; 2], response: [u8; 2]) -> OperationResult {
+        if self.count >= self.depth {
+            let expected_index = self.count - self.depth;
+            let expected = self.sent[expected_index % HISTORY_LEN];
+            if response != expected {
+                if self.established
+                    && (response == [0x00u8, 0x00u8] || self.sent_before(expected_index, response))
+                {
+                    return Err(Error::UnexpectedReset);
+                }
+                return Err(Error::CommunicationError {
+                    expected,
+                    received: response,
+                });
+            }
+            self.established = true;
+        }
+        self.sent[self.count % HISTORY_LEN] = frame;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Returns whether `response` equals one of the frames kept in the history which have
+    /// been sent before the frame with the given index.
+    fn sent_before(&self, index: usize, response: [u8; 2]) -> bool {
+        let oldest = self.count.saturating_sub(HISTORY_LEN);
+        (oldest..index).any(|i| self.sent[i % HISTORY_LEN] == response)
+    }
+}
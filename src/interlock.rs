@@ -0,0 +1,46 @@
+use crate::Error;
+use embedded_hal::digital::{InputPin, PinState};
+
+/// The `Interlock` trait represents an external safety interlock which inhibits the
+/// LED outputs of the BD18378 LED Driver IC while it is open.
+pub trait Interlock {
+    /// Returns whether the interlock is currently open, i.e. LED output must be inhibited.
+    fn is_open(&mut self) -> Result<bool, Error>;
+}
+
+/// The `NoInterlock` struct is used when no external interlock is connected.
+/// It never reports an open interlock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoInterlock;
+
+impl Interlock for NoInterlock {
+    fn is_open(&mut self) -> Result<bool, Error> {
+        Ok(false)
+    }
+}
+
+/// The `InterlockPin` struct represents an interlock connected to a GPIO input pin.
+pub struct InterlockPin<PIN: InputPin> {
+    pin: PIN,
+    open_state: PinState,
+}
+
+impl<PIN: InputPin> InterlockPin<PIN> {
+    /// Creates a new instance of the `InterlockPin` struct. The interlock is considered
+    /// open while the pin reads `open_state`.
+    pub fn new(pin: PIN, open_state: PinState) -> Self {
+        InterlockPin { pin, open_state }
+    }
+
+    /// Releases the input pin.
+    pub fn release(self) -> PIN {
+        self.pin
+    }
+}
+
+impl<PIN: InputPin> Interlock for InterlockPin<PIN> {
+    fn is_open(&mut self) -> Result<bool, Error> {
+        let high = self.pin.is_high().map_err(|_| Error::PinError)?;
+        Ok(PinState::from(high) == self.open_state)
+    }
+}
@@ -3,12 +3,55 @@
 //! This driver is designed to be used with embedded systems and is compatible
 //! with the `embedded-hal` crate.
 
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use crate::registers::WriteRegister;
-use embedded_hal::spi::SpiDevice;
+use crate::calibration::{CalibrationModel, LedBin};
+use crate::channel::{ChannelGroup, ChannelIndex};
+use crate::config::Config;
+use crate::echo::EchoValidator;
+use crate::interlock::{Interlock, NoInterlock};
+use crate::observer::{NoObserver, WriteObserver};
+use crate::pattern::TestPattern;
+use crate::registers::{ReadRegister, WriteRegister};
+use crate::state::DriverState;
+use crate::status::{ChannelFaults, Status};
+use core::borrow::BorrowMut;
+use core::marker::PhantomData;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::InputPin;
+use embedded_hal::spi::{Error as _, ErrorKind, Mode, SpiDevice, MODE_0};
 
+#[cfg(feature = "async")]
+pub mod asynch;
+pub mod builder;
+pub mod calibration;
+pub mod chain;
+pub mod channel;
+pub mod config;
+pub mod dry_run;
+mod echo;
+pub mod interlock;
+pub mod observer;
+pub mod pattern;
 pub mod registers;
+pub mod state;
+pub mod status;
+
+/// Returns the SPI mode expected by the BD18378 LED Driver IC.
+///
+/// The IC samples data on the rising edge of SCLK, which idles low (CPOL = 0, CPHA = 0).
+/// Every frame consists of 16 bits, transferred MSB first as two 8-bit words.
+///
+/// `SpiDevice` does not expose the bus configuration, so the driver cannot verify the
+/// mode itself. Use this when configuring the SPI bus, e.g.:
+///
+/// ```
+/// let mode = bd18378::expected_spi_mode();
+/// assert_eq!(mode, embedded_hal::spi::MODE_0);
+/// ```
+pub const fn expected_spi_mode() -> Mode {
+    MODE_0
+}
 
 /// The number of LED channels per register.
 const CHANNELS_PER_REGISTER: usize = 6;
@@ -16,15 +59,131 @@ const CHANNELS_PER_REGISTER: usize = 6;
 /// The total number of LED channels in the BD18378 LED Driver IC.
 const CHANNELS_PER_IC: usize = 12;
 
+/// The number of LED channels of the BD18378 LED Driver IC, i.e. the number of valid
+/// channel indices.
+///
+/// ```
+/// let mut mask = 0u16;
+/// for ch in 0..bd18378::CHANNEL_COUNT {
+///     mask |= 1 << ch;
+/// }
+/// assert_eq!(mask, 0x0FFF);
+/// ```
+pub const CHANNEL_COUNT: usize = CHANNELS_PER_IC;
+
+/// The number of channel groups, each controlled by its own channel enable register.
+const CHANNEL_GROUPS: usize = CHANNELS_PER_IC / CHANNELS_PER_REGISTER;
+
+/// The bitmask covering all LED channels of the BD18378 LED Driver IC.
+const ALL_CHANNELS_MASK: u16 = (1 << CHANNELS_PER_IC) - 1;
+
+/// The largest calibration value, the calibration registers use the lower 6 bits only.
+pub const MAX_CALIBRATION: u8 = 0b0011_1111u8;
+
+/// The value written to the `StatusReset` register to reset all status flags.
+const STATUS_RESET_VALUE: u8 = 0b0011_1111u8;
+
+/// The value written to the `SoftwareReset` register to trigger a software reset.
+const SOFTWARE_RESET_VALUE: u8 = 0b1010_0001u8;
+
+/// The value written to the `WriteLock` or `WriteUnlock` register to lock or unlock all
+/// register groups: bit 4 EN_ERR, bit 3 PWM_MAP, bit 2 EN_CH, bit 1 CAL and bit 0 DIAG.
+const REGISTER_LOCK_ALL: u8 = 0b0001_1111u8;
+
+/// The frame sent to clock out a response, e.g. the value of a register read, or to
+/// devices of a chain which are not addressed. Reading the status register does not
+/// change the state of the IC.
+const NOP_FRAME: [u8; 2] = [ReadRegister::Status as u8, 0x00u8];
+
+/// The time in microseconds given to the fault detection of the IC after a channel has
+/// been enabled during `self_test()`.
+const SELF_TEST_DETECTION_TIME_US: u32 = 1_000;
+
+/// The number of `SoftwareReset` writes at the start of the initialization sequence.
+const INIT_LEADING_RESETS: usize = 2;
+
+/// The maximum supported depth of the response echo pipeline.
+const MAX_ECHO_DEPTH: usize = 4;
+
+/// The number of register writes of the initialization sequence.
+///
+/// ```
+/// assert_eq!(bd18378::init_sequence().len(), bd18378::INIT_SEQUENCE_LEN);
+/// ```
+pub const INIT_SEQUENCE_LEN: usize = 15;
+
+/// The number of SPI transactions performed by `Bd18378::init()`: one per write of the
+/// initialization sequence, followed by the status reset and the two frames of the
+/// status read back. This can e.g. be used to size test fixtures.
+pub const INIT_SPI_TRANSACTION_COUNT: usize = INIT_SEQUENCE_LEN + 3;
+
+/// The initialization sequence for the BD18378 LED Driver IC.
+const INIT_SEQUENCE: [(WriteRegister, u8); INIT_SEQUENCE_LEN] = [
+    (WriteRegister::SoftwareReset, SOFTWARE_RESET_VALUE),
+    (WriteRegister::SoftwareReset, SOFTWARE_RESET_VALUE),
+    (WriteRegister::ReservedB5, 0b1001_1110u8),
+    (WriteRegister::ReservedB6, 0b0000_0000u8),
+    (WriteRegister::ReservedB5, 0b1001_1110u8),
+    (WriteRegister::ReservedB7, 0b0000_0000u8),
+    (WriteRegister::ReservedB5, 0b1001_1110u8),
+    (WriteRegister::ReservedB8, 0b0000_0000u8),
+    (WriteRegister::ReservedB5, 0b1001_1110u8),
+    (WriteRegister::ReservedB9, 0b0000_0000u8),
+    (WriteRegister::Reserved79, 0b1101_0110u8),
+    (WriteRegister::Reserved7A, 0b0000_0000u8),
+    (WriteRegister::Reserved79, 0b1101_0110u8),
+    (WriteRegister::Reserved7B, 0b0000_0000u8),
+    (WriteRegister::SoftwareReset, SOFTWARE_RESET_VALUE),
+];
+
+/// Returns the initialization sequence written by `Bd18378::init()`, e.g. to inspect or
+/// log the frames sent to the IC.
+///
+/// Each entry consists of the register and the value written to it. After the sequence,
+/// `init()` additionally resets the status flags and reads back the status register.
+pub const fn init_sequence() -> [(WriteRegister, u8); INIT_SEQUENCE_LEN] {
+    INIT_SEQUENCE
+}
+
+/// Checks the status read back after the status flags have been reset at the end of
+/// the initialization.
+///
+/// The power-on reset flag is only set by a power-on or software reset, so if it is still
+/// set, resetting the status flags did not take effect (datasheet p.12). Fault flags are
+/// re-evaluated right after the reset, e.g. for an open LED string, and are not checked.
+fn check_init_status(status: Status) -> OperationResult {
+    if status.power_on_reset() {
+        return Err(Error::InitFailed);
+    }
+    Ok(())
+}
+
 /// The `Error` enum represents various error types that can occur during
 /// communication with the BD18378 LED Driver IC.
+///
+/// The `Display` implementation provides short, human-readable messages. Their wording
+/// is considered part of the public API and is kept stable.
+///
+/// The enum is marked `#[non_exhaustive]`, so new variants can be added in minor
+/// releases. Matches on it outside of this crate need a wildcard arm.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum Error {
     /// Indicates a bus error during SPI communication coming from the used SPI device.
-    SpiError,
+    ///
+    /// The kind of the underlying SPI error is kept, e.g. to tell an overrun from a
+    /// chip select fault.
+    SpiError(#[cfg_attr(feature = "defmt", defmt(Debug2Format))] ErrorKind),
 
     /// Indicates a communication error during SPI communication due to an unexpected response.
-    CommunicationError,
+    ///
+    /// `expected` holds the frame the device was expected to echo, i.e. the register
+    /// address and value sent before, and `received` holds the frame actually received.
+    CommunicationError {
+        expected: [u8; 2],
+        received: [u8; 2],
+    },
 
     /// Indicates that the device was not in an initialized state after completing the initialization sequence.
     InitFailed,
@@ -34,67 +193,686 @@ pub enum Error {
 
     /// Indicates that the specified channel index is invalid.
     InvalidChannel,
+
+    /// Indicates that an operation was aborted by the caller before it could complete.
+    Timeout,
+
+    /// Indicates that an LED bin is not covered by the used calibration model.
+    InvalidBin,
+
+    /// Indicates an error while accessing a GPIO pin passed to the driver.
+    PinError,
+
+    /// Indicates that the device was reset unexpectedly, detected by its echo pipeline restarting
+    /// during the initialization sequence. After the initialization, a reset is detected by
+    /// the power-on reset flag, see `refresh_initialized_state()` and `set_auto_status_check()`.
+    UnexpectedReset,
+
+    /// Indicates that the LED outputs are inhibited because the external interlock is open.
+    Interlock,
+
+    /// Indicates that an argument passed to the driver is outside of its valid range.
+    InvalidArgument,
+
+    /// Indicates that an open, a short or a short to ground was detected on an LED channel
+    /// during `self_test()`.
+    ChannelFault {
+        /// The index of the faulty channel.
+        channel: usize,
+    },
+
+    /// Indicates that the status register reported a fault flag after a write, see
+    /// `set_auto_status_check()`.
+    Fault {
+        /// The raw value of the status register.
+        status: u8,
+    },
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let message = match self {
+            Error::SpiError(_) => "SPI bus error",
+            Error::CommunicationError { .. } => "unexpected response from device",
+            Error::InitFailed => "device initialization failed",
+            Error::NotInitialized => "device not initialized",
+            Error::InvalidChannel => "invalid channel index",
+            Error::Timeout => "operation aborted",
+            Error::InvalidBin => "LED bin not covered by calibration model",
+            Error::PinError => "GPIO pin error",
+            Error::UnexpectedReset => "unexpected device reset",
+            Error::Interlock => "interlock open",
+            Error::InvalidArgument => "argument out of range",
+            Error::ChannelFault { .. } => "LED channel fault",
+            Error::Fault { .. } => "device reported a fault",
+        };
+        f.write_str(message)
+    }
+}
+
+// `std::error::Error` is a re-export of this trait, so the impl is available with `std` as well.
+impl core::error::Error for Error {}
+
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(error: Error) -> Self {
+        std::io::Error::other(error)
+    }
+}
+
+/// The `UpdateOrder` enum selects the order in which the two channel enable registers
+/// are written when applying the cached channel states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateOrder {
+    /// Write `ChannelEnable00To05` first, then `ChannelEnable06To11`.
+    Default,
+
+    /// Write the register disabling more channels first, minimizing the number of
+    /// simultaneously enabled channels during the transition.
+    OffFirst,
+
+    /// Write the register enabling more channels first.
+    OnFirst,
 }
 
 /// The `OperationResult` type represents the result of an operation on the BD18378 LED Driver IC.
 pub type OperationResult = Result<(), Error>;
 
 /// The `Bd18378` struct represents the ROHM BD18378 LED Driver IC.
-pub struct Bd18378<'a, SPI: SpiDevice> {
-    spi: &'a mut SPI,
+///
+/// By default the driver borrows the SPI device, see `Bd18378::new()`. Alternatively
+/// it can take ownership of the SPI device, see `Bd18378Owned`.
+pub struct Bd18378<
+    'a,
+    SPI: SpiDevice,
+    IL: Interlock = NoInterlock,
+    S: BorrowMut<SPI> = &'a mut SPI,
+    O: WriteObserver = NoObserver,
+> {
+    spi: S,
+    interlock: IL,
+    echo_depth: usize,
     is_initialized: bool,
     channel_enable: [bool; CHANNELS_PER_IC],
+    channel_calibration: [Option<u8>; CHANNELS_PER_IC],
+    channel_group_values: [Option<u8>; CHANNEL_GROUPS],
+    last_response: [u8; 2],
+    write_count: u32,
+    auto_status_check: bool,
+    registers_locked: bool,
+    auto_unlock: bool,
+    relock_pending: bool,
+    write_observer: O,
+    _spi: PhantomData<(&'a (), SPI)>,
+}
+
+/// The `Bd18378Owned` type represents the ROHM BD18378 LED Driver IC owning its SPI device.
+///
+/// Unlike the borrowing driver it has no lifetime bound to the SPI device, so it can e.g.
+/// be stored in a struct or a static cell.
+pub type Bd18378Owned<SPI, IL = NoInterlock, O = NoObserver> = Bd18378<'static, SPI, IL, SPI, O>;
+
+impl<SPI: SpiDevice, IL: Interlock, S: BorrowMut<SPI>, O: WriteObserver> core::fmt::Debug
+    for Bd18378<'_, SPI, IL, S, O>
+{
+    /// Formats the initialized flag and the pending channel states, the SPI device, the
+    /// interlock and the write observer are omitted.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Bd18378")
+            .field("is_initialized", &self.is_initialized)
+            .field(
+                "channel_mask",
+                &format_args!("{:#014b}", self.enabled_channels_mask()),
+            )
+            .finish_non_exhaustive()
+    }
 }
 
 impl<'a, SPI: SpiDevice> Bd18378<'a, SPI> {
     /// Creates a new instance of the `Bd18378` struct. It takes a mutable reference
     /// to a SPI device as an argument.
     pub fn new(spi: &'a mut SPI) -> Self {
+        Self::new_with_interlock(spi, NoInterlock)
+    }
+
+    /// Creates a new instance of the `Bd18378` struct and initializes the IC.
+    /// Returns the initialized driver or the error of the initialization sequence.
+    pub fn new_initialized(spi: &'a mut SPI) -> Result<Self, Error> {
+        let mut driver = Self::new(spi);
+        driver.init()?;
+        Ok(driver)
+    }
+}
+
+impl<'a, SPI: SpiDevice, IL: Interlock> Bd18378<'a, SPI, IL> {
+    /// Creates a new instance of the `Bd18378` struct guarded by an external safety
+    /// interlock. It takes a mutable reference to a SPI device and the interlock as arguments.
+    ///
+    /// While the interlock is open, `update_all_channels()` and all methods applying
+    /// channel states through it disable all LED outputs and return `Error::Interlock`.
+    pub fn new_with_interlock(spi: &'a mut SPI, interlock: IL) -> Self {
+        Self::from_parts(spi, interlock)
+    }
+}
+
+impl<'a, SPI: SpiDevice, IL: Interlock, O: WriteObserver> Bd18378<'a, SPI, IL, &'a mut SPI, O> {
+    /// Consumes the driver and returns the SPI device, e.g. to reuse the bus for another device.
+    ///
+    /// *Note: The IC keeps its current state, the interlock and the write observer (if any)
+    /// are dropped.*
+    pub fn release(self) -> &'a mut SPI {
+        self.spi
+    }
+}
+
+impl<SPI: SpiDevice> Bd18378Owned<SPI> {
+    /// Creates a new instance of the `Bd18378` struct taking ownership of the SPI device.
+    ///
+    /// Use `new()` instead to share the bus with other devices.
+    pub fn new_owned(spi: SPI) -> Self {
+        Self::new_owned_with_interlock(spi, NoInterlock)
+    }
+}
+
+impl<SPI: SpiDevice, IL: Interlock> Bd18378Owned<SPI, IL> {
+    /// Creates a new instance of the `Bd18378` struct taking ownership of the SPI device,
+    /// guarded by an external safety interlock. See `new_with_interlock()`.
+    pub fn new_owned_with_interlock(spi: SPI, interlock: IL) -> Self {
+        Self::from_parts(spi, interlock)
+    }
+}
+
+impl<SPI: SpiDevice, IL: Interlock, O: WriteObserver> Bd18378Owned<SPI, IL, O> {
+    /// Consumes the driver and returns the owned SPI device.
+    ///
+    /// *Note: The IC keeps its current state, the interlock and the write observer (if any)
+    /// are dropped.*
+    pub fn into_spi(self) -> SPI {
+        self.spi
+    }
+}
+
+impl<'a, SPI: SpiDevice, IL: Interlock, S: BorrowMut<SPI>> Bd18378<'a, SPI, IL, S> {
+    /// Helper function to create a driver from its SPI device handle and interlock.
+    fn from_parts(spi: S, interlock: IL) -> Self {
         Bd18378 {
             spi,
+            interlock,
+            echo_depth: 1,
             is_initialized: false,
             channel_enable: [false; CHANNELS_PER_IC],
+            channel_calibration: [None; CHANNELS_PER_IC],
+            channel_group_values: [None; CHANNEL_GROUPS],
+            last_response: [0x00u8, 0x00u8],
+            write_count: 0,
+            auto_status_check: false,
+            registers_locked: false,
+            auto_unlock: false,
+            relock_pending: false,
+            write_observer: NoObserver,
+            _spi: PhantomData,
         }
     }
+}
 
+impl<'a, SPI: SpiDevice, IL: Interlock, S: BorrowMut<SPI>, O: WriteObserver>
+    Bd18378<'a, SPI, IL, S, O>
+{
     /// Initializes the BD18378 LED Driver IC by writing a sequence of values to its registers.
     /// The sequence is documented in the datasheet of the IC.
+    /// Afterwards the status flags are reset and the status register is read back. If the
+    /// power-on reset flag is still set, `Error::InitFailed` is returned.
     /// Returns an `OperationResult` indicating success or failure of the initialization sequence.
     pub fn init(&mut self) -> OperationResult {
-        let mut old_data = [0x00u8, 0x00u8];
-        let seq = Self::get_init_sequence();
-        let mut first = true;
-        for (reg, value) in seq.iter() {
-            let data = self.write_register(*reg, *value)?;
-            // Validate the SPI transfer response by comparing it with the previous transaction's data.
-            // This ensures the integrity of the communication sequence and guards against unexpected
-            // responses from the device, which could indicate a communication error.
-            if !first && data != old_data {
-                return Err(Error::CommunicationError);
+        self.init_with_abort(|| false)
+    }
+
+    /// Initializes the BD18378 LED Driver IC like `init()`, but allows the caller to
+    /// cancel the sequence cooperatively.
+    ///
+    /// The `should_abort` closure is polled before every register write of the sequence.
+    /// If it returns `true`, the initialization is aborted with `Error::Timeout` and the
+    /// device stays uninitialized. This can e.g. be driven by a hardware timer to bound
+    /// the time spent in the initialization sequence.
+    pub fn init_with_abort(&mut self, should_abort: impl FnMut() -> bool) -> OperationResult {
+        self.run_init_sequence(&Self::get_init_sequence(), true, should_abort)
+    }
+
+    /// Initializes the BD18378 LED Driver IC like `init()` and applies the startup state
+    /// described by `config` before returning.
+    ///
+    /// After the initialization sequence, the calibration values of all channels are
+    /// written, followed by the channel enable registers. The channel mask is validated
+    /// before the IC is accessed, setting any of the bits 12 to 15 results in
+    /// `Error::InvalidChannel`.
+    pub fn init_with_config(&mut self, config: &Config) -> OperationResult {
+        Self::check_channel_mask(config.channel_mask)?;
+
+        self.init()?;
+
+        let write_count = self.write_count;
+        for (ch, value) in config.calibration.iter().enumerate() {
+            self.write_channel_calibration(ch, *value)?;
+        }
+        self.load_channel_mask(config.channel_mask);
+        self.write_channel_groups(UpdateOrder::Default)?;
+        self.finish_writes(write_count)
+    }
+
+    /// Runs a self-test of the BD18378 LED Driver IC and the connected LEDs, e.g. for
+    /// production line bring-up.
+    ///
+    /// The IC is initialized, then each channel is enabled on its own, one after another.
+    /// Before a channel is enabled, the status flags are reset, so faults latched for the
+    /// previous channel are not reported again. After waiting 1 ms via `delay` for the
+    /// fault detection, the open and short detection registers are checked for the bit of
+    /// the channel (see `read_channel_faults()`) and the status register for a short to
+    /// ground. The test stops at the first faulty channel, returning `Error::ChannelFault`
+    /// with its index. Any other error is returned as is.
+    ///
+    /// All channels are disabled when the test ends, also after a failure, and the
+    /// previously cached channel states are discarded.
+    pub fn self_test(&mut self, delay: &mut impl DelayNs) -> OperationResult {
+        self.init()?;
+
+        let result = self.test_channels(delay);
+        self.channel_enable = [false; CHANNELS_PER_IC];
+        let cleanup = self.write_channel_groups(UpdateOrder::Default);
+        result?;
+        cleanup?;
+        Ok(())
+    }
+
+    /// Re-initializes the BD18378 LED Driver IC and restores the previous channel states
+    /// and calibration values, e.g. to recover from a fault.
+    ///
+    /// The cached state is saved, the initialization sequence is run like in `init()`, and
+    /// afterwards all calibration values written before are written again, followed by the
+    /// channel enable registers. Channels without a known calibration value keep their
+    /// reset default.
+    ///
+    /// *Note: The initialization sequence resets the IC, so the LEDs are off until the
+    /// channel enable registers are restored.*
+    pub fn reinit_preserving_channels(&mut self) -> OperationResult {
+        let channel_enable = self.channel_enable;
+        let channel_calibration = self.channel_calibration;
+
+        self.init()?;
+
+        let write_count = self.write_count;
+        for (ch, calibration) in channel_calibration.iter().enumerate() {
+            if let Some(calibration) = calibration {
+                self.write_channel_calibration(ch, *calibration)?;
             }
-            old_data = [*reg as u8, *value];
-            first = false;
         }
+        self.channel_enable = channel_enable;
+        self.write_channel_groups(UpdateOrder::Default)?;
 
-        self.reset_status_register()?;
-        self.is_initialized = true;
+        self.finish_writes(write_count)
+    }
+
+    /// Initializes the BD18378 LED Driver IC like `init()`, but without validating the
+    /// echoed responses of the initialization sequence.
+    ///
+    /// This is intended for boards which do not echo the previous frame as expected. The
+    /// status flags are still reset and checked, so `Error::InitFailed` is returned if the
+    /// IC did not reach a good state. Prefer `init()` wherever possible, as communication
+    /// errors during the sequence go unnoticed.
+    pub fn init_lenient(&mut self) -> OperationResult {
+        self.run_init_sequence(&Self::get_init_sequence(), false, || false)
+    }
+
+    /// Initializes the BD18378 LED Driver IC like `init()`, but repeats the full sequence
+    /// after transient communication failures.
+    ///
+    /// Up to `attempts` runs of the sequence are made. A run failing with
+    /// `Error::CommunicationError` or `Error::SpiError` is retried, any other error is
+    /// returned immediately. If all attempts fail, the error of the last attempt is returned.
+    /// Passing 0 attempts results in `Error::InvalidArgument`.
+    pub fn init_with_retries(&mut self, attempts: u8) -> OperationResult {
+        if attempts == 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut result = Ok(());
+        for _ in 0..attempts {
+            result = self.init();
+            match result {
+                Err(Error::CommunicationError { .. }) | Err(Error::SpiError(_)) => continue,
+                _ => break,
+            }
+        }
+        result
+    }
+
+    /// Initializes the BD18378 LED Driver IC like `init()`, but with a custom initialization
+    /// sequence instead of the default one, e.g. for board revisions requiring different
+    /// values for the reserved registers.
+    ///
+    /// The responses are validated and the status flags are reset and checked exactly as
+    /// in `init()`.
+    pub fn init_with_sequence(&mut self, seq: &[(WriteRegister, u8)]) -> OperationResult {
+        self.run_init_sequence(seq, true, || false)
+    }
+
+    /// Initializes the BD18378 LED Driver IC like `init()` and measures the duration of
+    /// the initialization sequence.
+    ///
+    /// The `now` closure is expected to return the current value of a monotonic tick
+    /// counter. It is sampled right before and right after the sequence, and the number
+    /// of elapsed ticks is returned on success. Counter wrap-around is handled.
+    pub fn init_timed(&mut self, now: impl Fn() -> u64) -> Result<u64, Error> {
+        let start = now();
+        self.init()?;
+        Ok(now().wrapping_sub(start))
+    }
+
+    /// Initializes the BD18378 LED Driver IC like `init()`, but skips the leading double
+    /// software reset if the IC is known to be freshly powered up.
+    ///
+    /// The `power_good` pin is sampled once. If it reads high, the IC is assumed to come
+    /// straight out of its power-on reset and the two leading `SoftwareReset` writes are
+    /// omitted. Otherwise the full initialization sequence is executed.
+    ///
+    /// *Note: The echo validation starts with the first frame actually written, so the
+    /// response to that frame is not validated.*
+    pub fn init_with_power_good<PIN: InputPin>(&mut self, power_good: &mut PIN) -> OperationResult {
+        let seq = Self::get_init_sequence();
+        let cold_boot = power_good.is_high().map_err(|_| Error::PinError)?;
+        let seq = if cold_boot {
+            &seq[INIT_LEADING_RESETS..]
+        } else {
+            &seq[..]
+        };
+        self.run_init_sequence(seq, true, || false)
+    }
+
+    /// Sets the depth of the response echo pipeline used to validate the initialization
+    /// sequence.
+    ///
+    /// By default the IC echoes the frame of the previous transaction (a depth of 1).
+    /// With a depth of `n`, each response is compared with the frame sent `n` transactions
+    /// before. Depths from 1 to 4 are supported, other values result in
+    /// `Error::InvalidArgument`.
+    pub fn set_echo_depth(&mut self, depth: usize) -> OperationResult {
+        if depth == 0 || depth > MAX_ECHO_DEPTH {
+            return Err(Error::InvalidArgument);
+        }
+
+        self.echo_depth = depth;
         Ok(())
     }
 
+    /// Checks whether a BD18378 LED Driver IC is present and answering on the SPI bus.
+    ///
+    /// Two `SoftwareReset` frames are written and the response to the second frame is
+    /// compared with the first frame. A present IC echoes the previous frame, while a
+    /// missing IC typically answers with a pulled-down or pulled-up bus.
+    ///
+    /// *Note: The software reset reverts the IC to its default state, so the driver is
+    /// marked as not initialized afterwards and `init()` has to be called again.*
+    pub fn probe_present(&mut self) -> Result<bool, Error> {
+        let frame = [WriteRegister::SoftwareReset as u8, SOFTWARE_RESET_VALUE];
+
+        self.is_initialized = false;
+        self.channel_calibration = [None; CHANNELS_PER_IC];
+        self.channel_group_values = [None; CHANNEL_GROUPS];
+        self.write_register(WriteRegister::SoftwareReset, SOFTWARE_RESET_VALUE)?;
+        let data = self.write_register(WriteRegister::SoftwareReset, SOFTWARE_RESET_VALUE)?;
+
+        Ok(data == frame)
+    }
+
+    /// Resets the BD18378 LED Driver IC via software reset and resets its status flags.
+    ///
+    /// All cached channel states and calibration values are cleared and the driver is
+    /// marked as not initialized. The IC is *not* re-initialized, so `init()` has to be
+    /// called before the next operation.
+    pub fn reset(&mut self) -> OperationResult {
+        self.is_initialized = false;
+        self.channel_enable = [false; CHANNELS_PER_IC];
+        self.channel_calibration = [None; CHANNELS_PER_IC];
+        self.channel_group_values = [None; CHANNEL_GROUPS];
+
+        for _ in 0..INIT_LEADING_RESETS {
+            self.write_register(WriteRegister::SoftwareReset, SOFTWARE_RESET_VALUE)?;
+        }
+        self.reset_status_register()
+    }
+
     /// Returns whether the BD18378 LED Driver IC is initialized.
     ///
     /// *Note: This is not a live view of the IC state, but rather a flag
     /// indicating whether the initialization sequence has been successfully executed.
-    /// This behavior might change in the future.*
+    /// Use `refresh_initialized_state()` to check the IC.*
     pub fn is_initialized(&self) -> bool {
         self.is_initialized
     }
 
+    /// Reads the status register to check whether the BD18378 LED Driver IC is still
+    /// configured, updates the initialized flag accordingly and returns it.
+    ///
+    /// The power-on reset flag (`POR_FLAG`, bit 0 of the status register) is set by an
+    /// undervoltage lockout or a software reset, which also reset all registers to their
+    /// defaults (datasheet p.7 and p.12). It is cleared and verified during initialization,
+    /// so a set flag means the IC went through a reset, e.g. a brown-out, and lost its
+    /// configuration. In that case the driver is marked as not initialized and the cached
+    /// register values are discarded. The pending channel states are kept, so they can be
+    /// applied again after `init()`. Other status flags do not affect the result.
+    ///
+    /// If the status cannot be read, the error is returned and the state is kept.
+    ///
+    /// *Note: A driver which has not been initialized stays uninitialized.*
+    pub fn refresh_initialized_state(&mut self) -> Result<bool, Error> {
+        let status = Status::from_bits(self.read_register(ReadRegister::Status)?);
+        if status.power_on_reset() {
+            self.is_initialized = false;
+            self.channel_calibration = [None; CHANNELS_PER_IC];
+            self.channel_group_values = [None; CHANNEL_GROUPS];
+        }
+        Ok(self.is_initialized)
+    }
+
+    /// Returns whether a single LED channel is marked as enabled.
+    ///
+    /// *Note: This is not a live view of the IC state, but rather the pending state
+    /// cached by the driver, which is applied to the IC by `update_all_channels()`.*
+    pub fn is_channel_enabled(&self, ch: impl ChannelIndex) -> Result<bool, Error> {
+        let ch = ch.index();
+        if ch >= self.channel_enable.len() {
+            return Err(Error::InvalidChannel);
+        }
+
+        Ok(self.channel_enable[ch])
+    }
+
+    /// Returns the enabled state of all LED channels as a bitmask.
+    ///
+    /// Bit 0 corresponds to channel 0, bit 11 to channel 11. The bits 12 to 15 are always
+    /// cleared.
+    ///
+    /// *Note: This is not a live view of the IC state, but rather the pending state
+    /// cached by the driver, which is applied to the IC by `update_all_channels()`.*
+    pub fn enabled_channels_mask(&self) -> u16 {
+        let mut mask = 0u16;
+        for (ch, enabled) in self.channel_enable.iter().enumerate() {
+            if *enabled {
+                mask |= 1 << ch;
+            }
+        }
+        mask
+    }
+
+    /// Returns a snapshot of the state cached by the driver, i.e. the initialized flag,
+    /// the pending channel states and the calibration values last written.
+    ///
+    /// This does not access the IC.
+    pub fn dump_state(&self) -> DriverState {
+        DriverState {
+            initialized: self.is_initialized,
+            channel_mask: self.enabled_channels_mask(),
+            calibration: self.channel_calibration,
+        }
+    }
+
+    /// Returns the response received during the most recent register write, e.g. to
+    /// debug a `CommunicationError`.
+    ///
+    /// As the IC echoes the previous frame, this is usually the frame written before the
+    /// most recent one. Register reads do not change the cached response. Before the first
+    /// write, `[0x00, 0x00]` is returned.
+    pub fn last_transfer_response(&self) -> [u8; 2] {
+        self.last_response
+    }
+
+    /// Enables or disables the automatic status check after every writing operation.
+    ///
+    /// When enabled, each operation of an initialized driver which writes to the IC, e.g.
+    /// `update_all_channels()` or `set_all_channel_calibration()`, is followed by a read of
+    /// the status register once all of its writes are done and the cached register values
+    /// are updated. The read takes two frames. If the power-on reset flag is set,
+    /// `Error::UnexpectedReset` is returned and, as with `refresh_initialized_state()`, the
+    /// driver is marked as not initialized and the cached register values are discarded. If
+    /// any other status flag is set, e.g. a short, open, weak LED supply or over-temperature,
+    /// `Error::Fault` is returned (see `Status::has_fault()`). All writes of the operation
+    /// have been performed in both cases. The writes of the initialization sequence and of
+    /// `self_test()` are not checked.
+    ///
+    /// The check is disabled by default, as it adds two frames to every writing operation.
+    pub fn set_auto_status_check(&mut self, enabled: bool) {
+        self.auto_status_check = enabled;
+    }
+
+    /// Locks all registers of the BD18378 LED Driver IC against writes, i.e. the channel
+    /// enable, calibration, PWM mapping, error enable and diagnosis registers.
+    ///
+    /// Writes to locked registers are ignored by the IC. The registers are unlocked after
+    /// a reset, so `init()` starts with unlocked registers. See `set_auto_unlock()` to keep
+    /// using the high-level API while the registers are locked.
+    pub fn lock_registers(&mut self) -> OperationResult {
+        self.check_initialized()?;
+
+        let write_count = self.write_count;
+        self.relock_pending = false;
+        self.write_register(WriteRegister::WriteLock, REGISTER_LOCK_ALL)?;
+        self.registers_locked = true;
+        self.finish_writes(write_count)
+    }
+
+    /// Unlocks all registers of the BD18378 LED Driver IC locked by `lock_registers()`.
+    pub fn unlock_registers(&mut self) -> OperationResult {
+        self.check_initialized()?;
+
+        let write_count = self.write_count;
+        self.relock_pending = false;
+        self.write_register(WriteRegister::WriteUnlock, REGISTER_LOCK_ALL)?;
+        self.registers_locked = false;
+        self.finish_writes(write_count)
+    }
+
+    /// Reads whether any register group of the BD18378 LED Driver IC is locked against
+    /// writes.
+    ///
+    /// The lock state is read back from the IC, so it also reflects a reset which unlocked
+    /// the registers after `lock_registers()`.
+    pub fn is_locked(&mut self) -> Result<bool, Error> {
+        self.check_initialized()?;
+
+        let unlocked = self.read_register(ReadRegister::UnlockState)?;
+        Ok(unlocked & REGISTER_LOCK_ALL != REGISTER_LOCK_ALL)
+    }
+
+    /// Enables or disables the automatic unlocking of locked registers.
+    ///
+    /// When enabled and the registers have been locked by `lock_registers()`, each
+    /// operation writing calibration or channel enable registers unlocks all registers
+    /// before its first write and locks them again after its last write. If the operation
+    /// fails, the registers stay unlocked until the end of the next writing operation.
+    ///
+    /// Automatic unlocking is disabled by default.
+    pub fn set_auto_unlock(&mut self, enabled: bool) {
+        self.auto_unlock = enabled;
+    }
+
+    /// Registers an observer which is called with the register and value of every
+    /// successful register write, e.g. for diagnostics. Replaces any previous observer.
+    ///
+    /// Any `FnMut(WriteRegister, u8)` closure can be used. The observer becomes part of the
+    /// driver type like the interlock, so no allocation is needed and a driver without an
+    /// observer carries no overhead.
+    pub fn with_write_observer<OBS: WriteObserver>(
+        self,
+        observer: OBS,
+    ) -> Bd18378<'a, SPI, IL, S, OBS> {
+        Bd18378 {
+            spi: self.spi,
+            interlock: self.interlock,
+            echo_depth: self.echo_depth,
+            is_initialized: self.is_initialized,
+            channel_enable: self.channel_enable,
+            channel_calibration: self.channel_calibration,
+            channel_group_values: self.channel_group_values,
+            last_response: self.last_response,
+            write_count: self.write_count,
+            auto_status_check: self.auto_status_check,
+            registers_locked: self.registers_locked,
+            auto_unlock: self.auto_unlock,
+            relock_pending: self.relock_pending,
+            write_observer: observer,
+            _spi: PhantomData,
+        }
+    }
+
+    /// Removes the write observer, if any.
+    pub fn without_write_observer(self) -> Bd18378<'a, SPI, IL, S> {
+        self.with_write_observer(NoObserver)
+    }
+
+    /// Returns the number of register writes performed by the driver so far, including
+    /// the writes of the initialization sequence. Register reads are not counted.
+    ///
+    /// Writes skipped because the register already holds the value are not counted, so
+    /// this can be used to verify that bus activity is actually reduced. The counter
+    /// wraps around on overflow.
+    pub fn write_count(&self) -> u32 {
+        self.write_count
+    }
+
+    /// Returns the number of LED channels of the IC, see `CHANNEL_COUNT`.
+    pub const fn channel_count(&self) -> usize {
+        CHANNEL_COUNT
+    }
+
+    /// Returns the values of the `ChannelEnable00To05` and `ChannelEnable06To11` registers
+    /// computed from the cached channel states, i.e. the values `update_all_channels()`
+    /// would write. Nothing is written to the IC.
+    ///
+    /// *Note: An open interlock is not taken into account.*
+    pub fn computed_enable_registers(&self) -> (u8, u8) {
+        // first 6 channels
+        let first_group_value = self.compute_channel_group_value(0, CHANNELS_PER_REGISTER, 0);
+        let second_group_value = self.compute_channel_group_value(
+            CHANNELS_PER_REGISTER,
+            CHANNELS_PER_IC,
+            CHANNELS_PER_REGISTER,
+        );
+        (first_group_value, second_group_value)
+    }
+
+    /// Returns an iterator over the `(channel_index, enabled)` pairs of all LED channels.
+    ///
+    /// *Note: This is not a live view of the IC state, but rather the pending state
+    /// cached by the driver, which is applied to the IC by `update_all_channels()`.*
+    pub fn channel_states(&self) -> impl Iterator<Item = (usize, bool)> + '_ {
+        self.channel_enable.iter().copied().enumerate()
+    }
+
     /// Enable a single LED channel by its index.
     ///
     /// *Note: This function does not update the LED channel state immediately.
     /// You need to call `update_all_channels()` to apply the changes.*
-    pub fn enable_channel(&mut self, ch: usize) -> OperationResult {
+    pub fn enable_channel(&mut self, ch: impl ChannelIndex) -> OperationResult {
+        let ch = ch.index();
         if ch >= self.channel_enable.len() {
             return Err(Error::InvalidChannel);
         }
@@ -105,11 +883,32 @@ impl<'a, SPI: SpiDevice> Bd18378<'a, SPI> {
         Ok(())
     }
 
+    /// Enable multiple LED channels by their indices.
+    ///
+    /// All indices are validated first, so an invalid index results in
+    /// `Error::InvalidChannel` without changing the state of any channel.
+    ///
+    /// *Note: This function does not update the LED channel state immediately.
+    /// You need to call `update_all_channels()` to apply the changes.*
+    pub fn enable_channels(&mut self, channels: &[usize]) -> OperationResult {
+        if channels.iter().any(|ch| *ch >= self.channel_enable.len()) {
+            return Err(Error::InvalidChannel);
+        }
+
+        self.check_initialized()?;
+
+        for ch in channels.iter() {
+            self.channel_enable[*ch] = true;
+        }
+        Ok(())
+    }
+
     /// Disable a single LED channel by its index.
     ///
     /// *Note: This function does not update the LED channel state immediately.
     /// You need to call `update_all_channels()` to apply the changes.*
-    pub fn disable_channel(&mut self, ch: usize) -> OperationResult {
+    pub fn disable_channel(&mut self, ch: impl ChannelIndex) -> OperationResult {
+        let ch = ch.index();
         if ch >= self.channel_enable.len() {
             return Err(Error::InvalidChannel);
         }
@@ -120,6 +919,71 @@ impl<'a, SPI: SpiDevice> Bd18378<'a, SPI> {
         Ok(())
     }
 
+    /// Enable a single LED channel by its index and apply the change to the IC immediately.
+    ///
+    /// Only the channel enable register of the group containing the channel is written,
+    /// and only if its value changed. If the driver is guarded by an interlock which is
+    /// currently open, all channels are disabled and `Error::Interlock` is returned like
+    /// in `update_all_channels()`.
+    pub fn enable_channel_and_update(&mut self, ch: impl ChannelIndex) -> OperationResult {
+        let ch = ch.index();
+        self.enable_channel(ch)?;
+
+        let write_count = self.write_count;
+        self.write_channel_group(ch / CHANNELS_PER_REGISTER)?;
+        self.finish_writes(write_count)
+    }
+
+    /// Disable a single LED channel by its index and apply the change to the IC immediately.
+    ///
+    /// See `enable_channel_and_update()` for the register writes.
+    pub fn disable_channel_and_update(&mut self, ch: impl ChannelIndex) -> OperationResult {
+        let ch = ch.index();
+        self.disable_channel(ch)?;
+
+        let write_count = self.write_count;
+        self.write_channel_group(ch / CHANNELS_PER_REGISTER)?;
+        self.finish_writes(write_count)
+    }
+
+    /// Toggle a single LED channel by its index.
+    ///
+    /// *Note: This function does not update the LED channel state immediately.
+    /// You need to call `update_all_channels()` to apply the changes.*
+    pub fn toggle_channel(&mut self, ch: impl ChannelIndex) -> OperationResult {
+        let ch = ch.index();
+        if ch >= self.channel_enable.len() {
+            return Err(Error::InvalidChannel);
+        }
+
+        self.check_initialized()?;
+
+        self.channel_enable[ch] = !self.channel_enable[ch];
+        Ok(())
+    }
+
+    /// Enable all LED channels.
+    ///
+    /// *Note: This function does not update the LED channel state immediately.
+    /// You need to call `update_all_channels()` to apply the changes.*
+    pub fn enable_all_channels(&mut self) -> OperationResult {
+        self.check_initialized()?;
+
+        self.channel_enable = [true; CHANNELS_PER_IC];
+        Ok(())
+    }
+
+    /// Disable all LED channels.
+    ///
+    /// *Note: This function does not update the LED channel state immediately.
+    /// You need to call `update_all_channels()` to apply the changes.*
+    pub fn disable_all_channels(&mut self) -> OperationResult {
+        self.check_initialized()?;
+
+        self.channel_enable = [false; CHANNELS_PER_IC];
+        Ok(())
+    }
+
     /// Update all LED channels based on their enabled state.
     ///
     /// This function maps the enabled state of each LED channel to specific bits
@@ -138,56 +1002,585 @@ impl<'a, SPI: SpiDevice> Bd18378<'a, SPI> {
     /// - If channel 6 is enabled, bit 0 of `ChannelEnable06To11` is set to 1.
     ///
     /// The function first processes channels 0 to 5, then channels 6 to 11, updating
-    /// the corresponding registers with the computed bit values.
+    /// the corresponding registers with the computed bit values. A register is only
+    /// written if its computed value differs from the value last written by the driver.
+    ///
+    /// If the driver is guarded by an interlock which is currently open, both registers
+    /// are written with all channels disabled instead and `Error::Interlock` is returned.
+    /// The cached channel states are kept and applied once the interlock is closed again.
     pub fn update_all_channels(&mut self) -> OperationResult {
+        self.update_all_channels_ordered(UpdateOrder::Default)
+    }
+
+    /// Update all LED channels like `update_all_channels()`, writing the two channel
+    /// enable registers in the given order.
+    ///
+    /// With `UpdateOrder::OffFirst`, the register turning off more channels (compared to
+    /// the value last written by the driver) is written first, so the number of
+    /// simultaneously enabled channels never exceeds the larger of the old and new state.
+    /// This helps to respect inrush limits for scenes close to the power budget.
+    /// `UpdateOrder::OnFirst` does the opposite. If nothing is known about the previous
+    /// register values, or both registers change equally, the default order is used.
+    pub fn update_all_channels_ordered(&mut self, order: UpdateOrder) -> OperationResult {
         self.check_initialized()?;
 
-        // first 6 channels
-        let first_group_value = self.compute_channel_group_value(0, CHANNELS_PER_REGISTER, 0);
-        self.write_register(WriteRegister::ChannelEnable00To05, first_group_value)?;
+        let write_count = self.write_count;
+        self.write_channel_groups(order)?;
+        self.finish_writes(write_count)
+    }
 
-        let second_group_value = self.compute_channel_group_value(
-            CHANNELS_PER_REGISTER,
-            CHANNELS_PER_IC,
-            CHANNELS_PER_REGISTER,
-        );
-        self.write_register(WriteRegister::ChannelEnable06To11, second_group_value)?;
+    /// Update all LED channels like `update_all_channels()` and report which channel
+    /// groups have actually been written.
+    ///
+    /// Returns a bitmask of all channels in the groups whose channel enable register was
+    /// written, i.e. `0x003F` for channels 0 to 5, `0x0FC0` for channels 6 to 11 and `0`
+    /// if both registers were skipped because their values did not change.
+    pub fn update_all_channels_report(&mut self) -> Result<u16, Error> {
+        self.check_initialized()?;
 
+        let write_count = self.write_count;
+        let written = self.write_channel_groups(UpdateOrder::Default)?;
+        self.finish_writes(write_count)?;
+        Ok(written)
+    }
+
+    /// Apply calibration values and enabled states for multiple LED channels at once.
+    ///
+    /// Each update is a tuple of channel index, enabled state and calibration value. All
+    /// channel indices are validated before anything is written, so an invalid index
+    /// results in `Error::InvalidChannel` without changing any state.
+    ///
+    /// The calibration values are written first (skipping unchanged values), followed by
+    /// the channel enable registers like in `update_all_channels()`, so at most two enable
+    /// writes are issued. The cached states of channels not contained in `updates` are applied as well.
+    pub fn apply_updates(&mut self, updates: &[(usize, bool, u8)]) -> OperationResult {
+        if updates.iter().any(|(ch, _, _)| *ch >= CHANNELS_PER_IC) {
+            return Err(Error::InvalidChannel);
+        }
+
+        self.check_initialized()?;
+
+        let write_count = self.write_count;
+        for (ch, enabled, calibration) in updates.iter() {
+            self.write_channel_calibration(*ch, *calibration)?;
+            self.channel_enable[*ch] = *enabled;
+        }
+
+        self.write_channel_groups(UpdateOrder::Default)?;
+        self.finish_writes(write_count)
+    }
+
+    /// Set the enabled state of all LED channels from a bitmask.
+    ///
+    /// Bit 0 of `mask` corresponds to channel 0, bit 11 to channel 11. Setting any of the
+    /// bits 12 to 15 results in `Error::InvalidChannel` without changing any state.
+    ///
+    /// *Note: This function does not update the LED channel state immediately.
+    /// You need to call `update_all_channels()` to apply the changes.*
+    pub fn set_channels(&mut self, mask: u16) -> OperationResult {
+        Self::check_channel_mask(mask)?;
+        self.check_initialized()?;
+
+        self.load_channel_mask(mask);
         Ok(())
     }
 
+    /// Set the enabled state of the six LED channels of a channel group from a bitmask.
+    ///
+    /// Bit 0 of `mask` corresponds to the first channel of the group, bit 5 to the last one.
+    /// The upper 2 bits are ignored, matching the layout of the channel enable registers.
+    /// The channels of the other group are not changed, so only the register of this group
+    /// is written by the next update.
+    ///
+    /// *Note: This function does not update the LED channel state immediately.
+    /// You need to call `update_all_channels()` to apply the changes.*
+    pub fn set_group(&mut self, group: ChannelGroup, mask: u8) -> OperationResult {
+        self.check_initialized()?;
+
+        for (ch, enabled) in self.channel_enable[group.channels()].iter_mut().enumerate() {
+            *enabled = mask & (1 << ch) != 0;
+        }
+        Ok(())
+    }
+
+    /// Set the enabled state of all LED channels from a bitmask and apply the result to
+    /// the IC, i.e. `set_channels()` followed by `update_all_channels()`.
+    ///
+    /// Bit 0 of `mask` corresponds to channel 0, bit 11 to channel 11. Setting any of the
+    /// bits 12 to 15 results in `Error::InvalidChannel` without changing any state.
+    pub fn write_channel_mask(&mut self, mask: u16) -> OperationResult {
+        self.set_channels(mask)?;
+        self.update_all_channels()
+    }
+
+    /// Enable all LED channels set in `mask` while preserving the state of all other
+    /// channels, and apply the result to the IC.
+    ///
+    /// Bit 0 of `mask` corresponds to channel 0, bit 11 to channel 11. Setting any of the
+    /// bits 12 to 15 results in `Error::InvalidChannel` without changing any state.
+    pub fn or_channels_mask(&mut self, mask: u16) -> OperationResult {
+        Self::check_channel_mask(mask)?;
+        self.check_initialized()?;
+
+        let combined = self.enabled_channels_mask() | mask;
+        self.load_channel_mask(combined);
+        self.update_all_channels()
+    }
+
+    /// Disable all LED channels set in `mask` while preserving the state of all other
+    /// channels, and apply the result to the IC.
+    ///
+    /// Bit 0 of `mask` corresponds to channel 0, bit 11 to channel 11. Setting any of the
+    /// bits 12 to 15 results in `Error::InvalidChannel` without changing any state.
+    pub fn clear_channels_mask(&mut self, mask: u16) -> OperationResult {
+        Self::check_channel_mask(mask)?;
+        self.check_initialized()?;
+
+        let remaining = self.enabled_channels_mask() & !mask;
+        self.load_channel_mask(remaining);
+        self.update_all_channels()
+    }
+
+    /// Apply a standard test pattern to the LED channels.
+    ///
+    /// The cached channel states are replaced by the pattern and written to the IC.
+    pub fn apply_test_pattern(&mut self, pattern: TestPattern) -> OperationResult {
+        let mask = pattern.mask()?;
+        self.check_initialized()?;
+
+        self.load_channel_mask(mask);
+        self.update_all_channels()
+    }
+
     /// Set the calibration value for a specific LED channel.
     ///
+    /// If the value equals the last value written to this channel by the driver, the
+    /// SPI write is skipped.
+    ///
     /// *Note: The calibration value is a 6-bit value, the upper 2 bits are ignored.
     /// E.g. a value of 0x80 will result in a calibration value of 0x00. *
-    pub fn set_channel_calibration(&mut self, ch: usize, calibration: u8) -> OperationResult {
+    pub fn set_channel_calibration(
+        &mut self,
+        ch: impl ChannelIndex,
+        calibration: u8,
+    ) -> OperationResult {
+        let ch = ch.index();
         if ch >= self.channel_enable.len() {
             return Err(Error::InvalidChannel);
         }
 
         self.check_initialized()?;
 
-        let register =
-            WriteRegister::try_from(WriteRegister::ChannelCalibration00 as u8 + ch as u8).unwrap();
+        let write_count = self.write_count;
+        self.write_channel_calibration(ch, calibration)?;
+        self.finish_writes(write_count)
+    }
 
-        self.write_register(register, calibration)?;
+    /// Set the calibration value for a specific LED channel, clamping it to the valid range.
+    ///
+    /// The calibration registers use the lower 6 bits only, so the valid range is
+    /// `0..=MAX_CALIBRATION` (0x00 to 0x3F). Larger values are written as `MAX_CALIBRATION`
+    /// instead of having their upper bits dropped. Returns whether the value was clamped.
+    pub fn set_channel_calibration_clamped(
+        &mut self,
+        ch: impl ChannelIndex,
+        calibration: u8,
+    ) -> Result<bool, Error> {
+        let clamped = calibration > MAX_CALIBRATION;
+        self.set_channel_calibration(ch, calibration.min(MAX_CALIBRATION))?;
+        Ok(clamped)
+    }
 
-        Ok(())
+    /// Returns the calibration value last written to a specific LED channel.
+    ///
+    /// Returns `None` if no calibration value was written by this driver instance since
+    /// the last initialization or reset of the IC.
+    ///
+    /// *Note: This is not a live view of the IC state, but rather the value last written
+    /// by this driver instance.*
+    pub fn get_channel_calibration(&self, ch: impl ChannelIndex) -> Result<Option<u8>, Error> {
+        let ch = ch.index();
+        if ch >= self.channel_calibration.len() {
+            return Err(Error::InvalidChannel);
+        }
+
+        Ok(self.channel_calibration[ch])
     }
 
     /// Set the calibration values for all LED channels.
     ///
+    /// Channels whose value equals the last value written by the driver are skipped.
+    ///
     /// *Note: The calibration value is a 6-bit value, the upper 2 bits are ignored.
     /// E.g. a value of 0x80 will result in a calibration value of 0x00. *
     pub fn set_all_channel_calibration(&mut self, calibration: &[u8; CHANNELS_PER_IC]) -> OperationResult {
         self.check_initialized()?;
 
-        for ch in 0..CHANNELS_PER_IC {
-            let register =
-                WriteRegister::try_from(WriteRegister::ChannelCalibration00 as u8 + ch as u8)
-                    .unwrap();
-            self.write_register(register, calibration[ch])?;
+        let write_count = self.write_count;
+        for (ch, value) in calibration.iter().enumerate() {
+            self.write_channel_calibration(ch, *value)?;
+        }
+
+        self.finish_writes(write_count)
+    }
+
+    /// Set the same calibration value for a contiguous range of LED channels.
+    ///
+    /// The range is half-open, i.e. `start` is inclusive and `end` is exclusive, so
+    /// `set_channel_calibration_range(0, 12, value)` covers all channels. An empty range
+    /// (`start == end`) writes nothing. Channels whose value equals the last value written
+    /// by the driver are skipped.
+    ///
+    /// Returns `Error::InvalidChannel` if `start` is larger than `end` or `end` exceeds
+    /// the number of channels.
+    ///
+    /// *Note: The calibration value is a 6-bit value, the upper 2 bits are ignored.
+    /// E.g. a value of 0x80 will result in a calibration value of 0x00. *
+    pub fn set_channel_calibration_range(
+        &mut self,
+        start: usize,
+        end: usize,
+        value: u8,
+    ) -> OperationResult {
+        if start > end || end > CHANNELS_PER_IC {
+            return Err(Error::InvalidChannel);
+        }
+
+        self.check_initialized()?;
+
+        let write_count = self.write_count;
+        for ch in start..end {
+            self.write_channel_calibration(ch, value)?;
+        }
+
+        self.finish_writes(write_count)
+    }
+
+    /// Set the calibration values for all LED channels as a linear gradient.
+    ///
+    /// Channel 0 receives `start`, channel 11 receives `end` and the channels in
+    /// between are linearly interpolated (rounded to the nearest code). A descending
+    /// gradient is obtained by passing a `start` value larger than `end`.
+    ///
+    /// Both values are validated before anything is written, a value larger than
+    /// `MAX_CALIBRATION` (0x3F) results in `Error::InvalidArgument`.
+    pub fn set_calibration_gradient(&mut self, start: u8, end: u8) -> OperationResult {
+        if start > MAX_CALIBRATION || end > MAX_CALIBRATION {
+            return Err(Error::InvalidArgument);
+        }
+
+        self.check_initialized()?;
+
+        let steps = (CHANNELS_PER_IC - 1) as i32;
+        let delta = end as i32 - start as i32;
+        let mut calibration = [0u8; CHANNELS_PER_IC];
+        for (ch, value) in calibration.iter_mut().enumerate() {
+            let scaled = delta * ch as i32;
+            // round half away from zero so the gradient is symmetric for both directions
+            let offset = if scaled >= 0 {
+                (scaled + steps / 2) / steps
+            } else {
+                (scaled - steps / 2) / steps
+            };
+            *value = (start as i32 + offset) as u8;
+        }
+
+        self.set_all_channel_calibration(&calibration)
+    }
+
+    /// Set the calibration values for all LED channels from a per-channel LED bin table.
+    ///
+    /// Each channel's bin is mapped to a calibration code via the given `model`. All bins
+    /// are validated before anything is written, so an unknown bin results in
+    /// `Error::InvalidBin` without touching the IC.
+    pub fn apply_bin_table(
+        &mut self,
+        bins: &[LedBin; CHANNELS_PER_IC],
+        model: &CalibrationModel,
+    ) -> OperationResult {
+        self.check_initialized()?;
+
+        let mut calibration = [0u8; CHANNELS_PER_IC];
+        for (value, bin) in calibration.iter_mut().zip(bins.iter()) {
+            *value = model.code_for(*bin).ok_or(Error::InvalidBin)?;
+        }
+
+        self.set_all_channel_calibration(&calibration)
+    }
+
+    /// Reads the raw value of the status register of the BD18378 LED Driver IC.
+    pub fn read_status(&mut self) -> Result<u8, Error> {
+        self.check_initialized()?;
+
+        self.read_register(ReadRegister::Status)
+    }
+
+    /// Reads and decodes the status register of the BD18378 LED Driver IC.
+    pub fn read_status_flags(&mut self) -> Result<Status, Error> {
+        let bits = self.read_status()?;
+        Ok(Status::from_bits(bits))
+    }
+
+    /// Acknowledges all latched fault flags by resetting the status register of the
+    /// BD18378 LED Driver IC.
+    ///
+    /// Faults which are still present are reported again on the next status read.
+    pub fn clear_faults(&mut self) -> OperationResult {
+        self.check_initialized()?;
+
+        let write_count = self.write_count;
+        self.reset_status_register()?;
+        self.finish_writes(write_count)
+    }
+
+    /// Reads the status register of the BD18378 LED Driver IC and acknowledges all latched
+    /// fault flags, returning the value that was read.
+    ///
+    /// The status register is read like `read_status()` and the `StatusReset` write follows
+    /// right after, the read always coming first. This keeps the window in which a newly
+    /// latched fault could be cleared without being reported as short as possible.
+    /// Faults which are still present are reported again on the next status read.
+    pub fn read_and_clear_status(&mut self) -> Result<u8, Error> {
+        self.check_initialized()?;
+
+        let status = self.read_register(ReadRegister::Status)?;
+        let write_count = self.write_count;
+        self.reset_status_register()?;
+        self.finish_writes(write_count)?;
+        Ok(status)
+    }
+
+    /// Reads the per-channel open and short circuit detection registers of the
+    /// BD18378 LED Driver IC.
+    ///
+    /// Each fault register covers a group of 6 channels, the lower register is mapped
+    /// to channels 0 to 5 and the upper register to channels 6 to 11 of the returned masks.
+    pub fn read_channel_faults(&mut self) -> Result<ChannelFaults, Error> {
+        self.check_initialized()?;
+
+        let short = self.read_channel_group_registers(
+            ReadRegister::ShortFault00To05,
+            ReadRegister::ShortFault06To11,
+        )?;
+        let open = self.read_channel_group_registers(
+            ReadRegister::OpenFault00To05,
+            ReadRegister::OpenFault06To11,
+        )?;
+
+        Ok(ChannelFaults { open, short })
+    }
+
+    /// Reads the raw value of a register of the BD18378 LED Driver IC.
+    ///
+    /// The register address is latched with a first frame, the IC then shifts out the
+    /// register address and value during the following frame (datasheet p.18). A status
+    /// read is used as the following frame. If the response does not carry the requested
+    /// register address, `Error::CommunicationError` is returned.
+    ///
+    /// This is a low-level access method which does not require the IC to be initialized.
+    /// Prefer the typed methods like `read_status_flags()` where available.
+    pub fn read_register(&mut self, register: ReadRegister) -> Result<u8, Error> {
+        let request = [register as u8, 0x00u8];
+        let mut data = request;
+        if let Err(e) = self.spi.borrow_mut().transfer_in_place(&mut data) {
+            return Err(Error::SpiError(e.kind()));
+        }
+
+        let mut response = NOP_FRAME;
+        if let Err(e) = self.spi.borrow_mut().transfer_in_place(&mut response) {
+            return Err(Error::SpiError(e.kind()));
+        }
+        if response[0] != request[0] {
+            return Err(Error::CommunicationError {
+                expected: request,
+                received: response,
+            });
+        }
+        Ok(response[1])
+    }
+
+    /// Writes a raw value to a register of the BD18378 LED Driver IC and returns the
+    /// response received during the transfer.
+    ///
+    /// **Advanced:** This is a low-level escape hatch, e.g. for bring-up, which bypasses
+    /// all validation of the high-level API. Writing arbitrary values, especially to the
+    /// reserved or reset registers, can leave the IC in an undefined state. Cached values
+    /// of a written calibration or channel enable register are discarded, so the next
+    /// high-level write to that register is always performed. A write to the
+    /// `SoftwareReset` register discards all cached register values and marks the driver
+    /// as not initialized, like `reset()`.
+    pub fn write_raw(&mut self, register: WriteRegister, value: u8) -> Result<[u8; 2], Error> {
+        self.check_initialized()?;
+
+        if register == WriteRegister::SoftwareReset {
+            self.is_initialized = false;
+            self.channel_calibration = [None; CHANNELS_PER_IC];
+            self.channel_group_values = [None; CHANNEL_GROUPS];
+        } else if let Some(cached) = self.cached_value(register) {
+            *cached = None;
+        }
+
+        let write_count = self.write_count;
+        let data = self.write_register(register, value)?;
+        self.finish_writes(write_count)?;
+        Ok(data)
+    }
+
+    /// Writes the given initialization sequence and, if `validate_echo` is set, validates
+    /// the echoed responses.
+    fn run_init_sequence(
+        &mut self,
+        seq: &[(WriteRegister, u8)],
+        validate_echo: bool,
+        mut should_abort: impl FnMut() -> bool,
+    ) -> OperationResult {
+        let mut echo = EchoValidator::new(self.echo_depth);
+        // the sequence resets the device, so previously written register values are lost
+        self.is_initialized = false;
+        self.channel_calibration = [None; CHANNELS_PER_IC];
+        self.channel_group_values = [None; CHANNEL_GROUPS];
+        self.registers_locked = false;
+        self.relock_pending = false;
+        for (reg, value) in seq.iter() {
+            if should_abort() {
+                return Err(Error::Timeout);
+            }
+            let data = self.write_register(*reg, *value)?;
+            // Validate the SPI transfer response by comparing it with the data sent `echo_depth`
+            // transactions before. This ensures the integrity of the communication sequence and
+            // guards against unexpected responses from the device, which could indicate a
+            // communication error.
+            if validate_echo {
+                echo.check([*reg as u8, *value], data)?;
+            }
+        }
+
+        if should_abort() {
+            return Err(Error::Timeout);
+        }
+        self.reset_status_register()?;
+
+        let status = self.read_register(ReadRegister::Status)?;
+        check_init_status(Status::from_bits(status))?;
+
+        self.is_initialized = true;
+        Ok(())
+    }
+
+    /// Enables each channel on its own and checks the fault detection registers and the
+    /// status register for a fault of that channel.
+    fn test_channels(&mut self, delay: &mut impl DelayNs) -> OperationResult {
+        for channel in 0..CHANNELS_PER_IC {
+            self.reset_status_register()?;
+            self.load_channel_mask(1 << channel);
+            self.write_channel_groups(UpdateOrder::Default)?;
+            delay.delay_us(SELF_TEST_DETECTION_TIME_US);
+
+            let faults = self.read_channel_faults()?;
+            // the per-channel registers do not cover shorts to ground, the status flag
+            // belongs to this channel as the status has been reset before enabling it
+            let status = Status::from_bits(self.read_register(ReadRegister::Status)?);
+            if (faults.open | faults.short) & (1 << channel) != 0 || status.short_to_ground() {
+                return Err(Error::ChannelFault { channel });
+            }
+        }
+        Ok(())
+    }
+
+    /// Helper function to set the enabled state of all channels from a bitmask.
+    fn load_channel_mask(&mut self, mask: u16) {
+        for (ch, enabled) in self.channel_enable.iter_mut().enumerate() {
+            *enabled = mask & (1 << ch) != 0;
+        }
+    }
+
+    /// Checks that a channel bitmask only contains valid channels.
+    fn check_channel_mask(mask: u16) -> OperationResult {
+        if mask & !ALL_CHANNELS_MASK != 0 {
+            return Err(Error::InvalidChannel);
+        }
+        Ok(())
+    }
+
+    /// Writes the cached channel states to both channel enable registers.
+    ///
+    /// Registers already holding the computed value are not written again. While the
+    /// interlock is open, all channels are written as disabled.
+    ///
+    /// Returns the bitmask of all channels in the groups which have been written.
+    fn write_channel_groups(&mut self, order: UpdateOrder) -> Result<u16, Error> {
+        let interlock_open = self.interlock.is_open()?;
+        let values = if interlock_open {
+            [0x00u8, 0x00u8]
+        } else {
+            let (first_group_value, second_group_value) = self.computed_enable_registers();
+            [first_group_value, second_group_value]
+        };
+
+        let registers = [
+            WriteRegister::ChannelEnable00To05,
+            WriteRegister::ChannelEnable06To11,
+        ];
+        let mut groups = [0usize, 1usize];
+        if order != UpdateOrder::Default {
+            // the balance of newly enabled versus newly disabled channels per group
+            let balance = |group: usize| match self.channel_group_values[group] {
+                Some(old) => {
+                    let on = (values[group] & !old).count_ones() as i32;
+                    let off = (old & !values[group]).count_ones() as i32;
+                    on - off
+                }
+                None => 0,
+            };
+            let (first, second) = (balance(0), balance(1));
+            let swap = match order {
+                UpdateOrder::OffFirst => second < first,
+                UpdateOrder::OnFirst => second > first,
+                UpdateOrder::Default => false,
+            };
+            if swap {
+                groups.swap(0, 1);
+            }
         }
+        let changed = |group: usize| self.channel_group_values[group] != Some(values[group]);
+        let group_mask = (1u16 << CHANNELS_PER_REGISTER) - 1;
+        let written = (0..CHANNEL_GROUPS)
+            .filter(|group| changed(*group))
+            .fold(0u16, |mask, group| mask | group_mask << (group * CHANNELS_PER_REGISTER));
+        let [first, second] = groups;
+        let (write_first, write_second) = (changed(first), changed(second));
+        // each frame needs its own chip select window, as the IC only latches the
+        // 16 most recent bits on the rising LATCH edge (datasheet p.18)
+        if write_first {
+            self.write_register(registers[first], values[first])?;
+        }
+        if write_second {
+            self.write_register(registers[second], values[second])?;
+        }
+        self.channel_group_values = [Some(values[0]), Some(values[1])];
+
+        if interlock_open {
+            return Err(Error::Interlock);
+        }
+        Ok(written)
+    }
+
+    /// Writes the cached channel states of a single group to its channel enable register,
+    /// unless the register already holds the computed value.
+    ///
+    /// While the interlock is open, both registers are written as disabled instead.
+    fn write_channel_group(&mut self, group: usize) -> OperationResult {
+        if self.interlock.is_open()? {
+            return self.write_channel_groups(UpdateOrder::Default).map(|_| ());
+        }
+
+        let start = group * CHANNELS_PER_REGISTER;
+        let value = self.compute_channel_group_value(start, start + CHANNELS_PER_REGISTER, start);
+        let register = [
+            WriteRegister::ChannelEnable00To05,
+            WriteRegister::ChannelEnable06To11,
+        ][group];
+        self.write_if_changed(register, value)?;
 
         Ok(())
     }
@@ -203,23 +1596,124 @@ impl<'a, SPI: SpiDevice> Bd18378<'a, SPI> {
         group_value
     }
 
+    /// Writes the calibration value of a channel unless it is already known to be set.
+    fn write_channel_calibration(&mut self, ch: usize, calibration: u8) -> OperationResult {
+        let register =
+            WriteRegister::try_from(WriteRegister::ChannelCalibration00 as u8 + ch as u8).unwrap();
+
+        self.write_if_changed(register, calibration)?;
+
+        Ok(())
+    }
+
+    /// Writes a value to a register unless the driver knows the register already holds it.
+    ///
+    /// Only the calibration and channel enable registers are cached, all other registers
+    /// are always written. Returns whether the register was written.
+    fn write_if_changed(&mut self, register: WriteRegister, value: u8) -> Result<bool, Error> {
+        if let Some(cached) = self.cached_value(register) {
+            if *cached == Some(value) {
+                return Ok(false);
+            }
+        }
+
+        self.write_register(register, value)?;
+        if let Some(cached) = self.cached_value(register) {
+            *cached = Some(value);
+        }
+
+        Ok(true)
+    }
+
+    /// Returns the cache entry holding the value last written to a register, if the
+    /// register is cached.
+    fn cached_value(&mut self, register: WriteRegister) -> Option<&mut Option<u8>> {
+        let address = register as u8;
+        let calibration =
+            WriteRegister::ChannelCalibration00 as u8..=WriteRegister::ChannelCalibration11 as u8;
+        if calibration.contains(&address) {
+            Some(&mut self.channel_calibration[(address - *calibration.start()) as usize])
+        } else if register == WriteRegister::ChannelEnable00To05 {
+            Some(&mut self.channel_group_values[0])
+        } else if register == WriteRegister::ChannelEnable06To11 {
+            Some(&mut self.channel_group_values[1])
+        } else {
+            None
+        }
+    }
+
     /// Writes a value to a specified register of the BD18378 LED Driver IC.
+    ///
+    /// If the registers are locked and automatic unlocking is enabled, the registers are
+    /// unlocked before the first calibration or channel enable write of an operation.
     fn write_register(&mut self, register: WriteRegister, value: u8) -> Result<[u8; 2], Error> {
+        if self.auto_unlock
+            && self.registers_locked
+            && !self.relock_pending
+            && self.cached_value(register).is_some()
+        {
+            self.relock_pending = true;
+            self.write_register(WriteRegister::WriteUnlock, REGISTER_LOCK_ALL)?;
+        }
+
         let mut data = [register as u8, value];
-        let result = self.spi.transfer_in_place(&mut data);
-        if result.is_ok() {
-            Ok(data)
-        } else {
-            Err(Error::SpiError)
+        let result = self.spi.borrow_mut().transfer_in_place(&mut data);
+        match result {
+            Ok(()) => {
+                self.last_response = data;
+                self.write_count = self.write_count.wrapping_add(1);
+                self.write_observer.on_write(register, value);
+            }
+            Err(e) => return Err(Error::SpiError(e.kind())),
+        }
+        Ok(data)
+    }
+
+    /// Completes the writes of an operation: locks the registers again if they have been
+    /// unlocked automatically, then reads the status register if the automatic status check
+    /// is enabled, the driver is initialized and any register has been written since the
+    /// write counter had the value `write_count`.
+    fn finish_writes(&mut self, write_count: u32) -> OperationResult {
+        if self.relock_pending {
+            self.relock_pending = false;
+            self.write_register(WriteRegister::WriteLock, REGISTER_LOCK_ALL)?;
+        }
+
+        if !self.auto_status_check || !self.is_initialized || self.write_count == write_count {
+            return Ok(());
         }
+
+        let status = Status::from_bits(self.read_register(ReadRegister::Status)?);
+        if status.power_on_reset() {
+            self.is_initialized = false;
+            self.channel_calibration = [None; CHANNELS_PER_IC];
+            self.channel_group_values = [None; CHANNEL_GROUPS];
+            return Err(Error::UnexpectedReset);
+        }
+        if status.has_fault() {
+            return Err(Error::Fault { status: status.bits() });
+        }
+        Ok(())
     }
 
     /// Resets the status register of the BD18378 LED Driver IC.
     fn reset_status_register(&mut self) -> OperationResult {
-        let _ = self.write_register(WriteRegister::StatusReset, 0b0011_1111u8)?;
+        let _ = self.write_register(WriteRegister::StatusReset, STATUS_RESET_VALUE)?;
         Ok(())
     }
 
+    /// Helper function to read a pair of per-group registers into a channel bitmask.
+    fn read_channel_group_registers(
+        &mut self,
+        lower: ReadRegister,
+        upper: ReadRegister,
+    ) -> Result<u16, Error> {
+        let group_mask = (1u8 << CHANNELS_PER_REGISTER) - 1;
+        let lower = self.read_register(lower)? & group_mask;
+        let upper = self.read_register(upper)? & group_mask;
+        Ok(lower as u16 | (upper as u16) << CHANNELS_PER_REGISTER)
+    }
+
     /// Checks if the BD18378 LED Driver IC is initialized before performing any operation.
     fn check_initialized(&self) -> OperationResult {
         if !self.is_initialized {
@@ -228,34 +1722,8 @@ impl<'a, SPI: SpiDevice> Bd18378<'a, SPI> {
         Ok(())
     }
 
-    /// A placeholder function for locking the BD18378 LED Driver IC's registers.
-    ///
-    /// This function is currently a no-op but is reserved for future functionality
-    /// where register locking might be required to prevent unintended modifications.
-    ///
-    /// *Note: This function is private and not used in the current implementation.*
-    fn _lock_register(&mut self) -> Result<(), ()> {
-        Ok(())
-    }
-
     /// Returns the initialization sequence for the BD18378 LED Driver IC.
-    const fn get_init_sequence() -> [(WriteRegister, u8); 15] {
-        [
-            (WriteRegister::SoftwareReset, 0b1010_0001u8),
-            (WriteRegister::SoftwareReset, 0b1010_0001u8),
-            (WriteRegister::ReservedB5, 0b1001_1110u8),
-            (WriteRegister::ReservedB6, 0b0000_0000u8),
-            (WriteRegister::ReservedB5, 0b1001_1110u8),
-            (WriteRegister::ReservedB7, 0b0000_0000u8),
-            (WriteRegister::ReservedB5, 0b1001_1110u8),
-            (WriteRegister::ReservedB8, 0b0000_0000u8),
-            (WriteRegister::ReservedB5, 0b1001_1110u8),
-            (WriteRegister::ReservedB9, 0b0000_0000u8),
-            (WriteRegister::Reserved79, 0b1101_0110u8),
-            (WriteRegister::Reserved7A, 0b0000_0000u8),
-            (WriteRegister::Reserved79, 0b1101_0110u8),
-            (WriteRegister::Reserved7B, 0b0000_0000u8),
-            (WriteRegister::SoftwareReset, 0b1010_0001u8),
-        ]
+    const fn get_init_sequence() -> [(WriteRegister, u8); INIT_SEQUENCE_LEN] {
+        init_sequence()
     }
 }
@@ -0,0 +1,25 @@
+use crate::registers::WriteRegister;
+
+/// The `WriteObserver` trait represents a hook which is called with every successful
+/// register write to the BD18378 LED Driver IC, e.g. for logging or diagnostics.
+///
+/// It is implemented for all `FnMut(WriteRegister, u8)` closures.
+pub trait WriteObserver {
+    /// Called with the register and the value after a write has been transferred.
+    fn on_write(&mut self, register: WriteRegister, value: u8);
+}
+
+/// The `NoObserver` struct is used when no write observer is registered.
+/// It ignores all writes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoObserver;
+
+impl WriteObserver for NoObserver {
+    fn on_write(&mut self, _register: WriteRegister, _value: u8) {}
+}
+
+impl<F: FnMut(WriteRegister, u8)> WriteObserver for F {
+    fn on_write(&mut self, register: WriteRegister, value: u8) {
+        self(register, value)
+    }
+}
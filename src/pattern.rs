@@ -0,0 +1,49 @@
+use crate::{Error, CHANNELS_PER_IC, CHANNELS_PER_REGISTER};
+
+/// The `TestPattern` enum represents standard channel patterns used during bring-up
+/// of boards using the BD18378 LED Driver IC.
+///
+/// The patterns are described in terms of the two channel groups of the IC, i.e.
+/// channels 0 to 5 form the first row and channels 6 to 11 the second row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestPattern {
+    /// All channels enabled.
+    AllOn,
+
+    /// All channels disabled.
+    AllOff,
+
+    /// Even channels enabled, odd channels disabled.
+    EvenOdd,
+
+    /// Even channels enabled in the first group and odd channels enabled in the second group.
+    Checkerboard,
+
+    /// Only the given channel enabled.
+    WalkingOne(usize),
+
+    /// Alternating bars of two enabled and two disabled channels, starting with
+    /// channels 0 and 1 enabled.
+    Bars,
+}
+
+impl TestPattern {
+    /// Returns the channel bitmask of the pattern, bit 0 corresponding to channel 0.
+    /// Returns `Error::InvalidChannel` for a `WalkingOne` pattern with an invalid channel.
+    pub fn mask(self) -> Result<u16, Error> {
+        let mask = match self {
+            TestPattern::AllOn => 0b1111_1111_1111,
+            TestPattern::AllOff => 0b0000_0000_0000,
+            TestPattern::EvenOdd => 0b0101_0101_0101,
+            TestPattern::Checkerboard => 0b10_1010 << CHANNELS_PER_REGISTER | 0b01_0101,
+            TestPattern::WalkingOne(ch) => {
+                if ch >= CHANNELS_PER_IC {
+                    return Err(Error::InvalidChannel);
+                }
+                1 << ch
+            }
+            TestPattern::Bars => 0b0011_0011_0011,
+        };
+        Ok(mask)
+    }
+}
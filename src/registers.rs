@@ -2,7 +2,8 @@ use strum::FromRepr;
 
 /// The `WriteRegister` enum represents various writeable registers
 /// of the ROHM BD18378 LED Driver IC, along with their corresponding hexadecimal addresses.
-#[derive(Debug, Clone, Copy, FromRepr)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromRepr)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum WriteRegister {
     
@@ -24,6 +25,10 @@ pub enum WriteRegister {
     ChannelEnable00To05 = 0x56,
     ChannelEnable06To11 = 0x57,
     
+    // Register write protection (datasheet p.11), 1 = lock or unlock the register group
+    WriteLock = 0x69,
+    WriteUnlock = 0x6A,
+    
     // IC reset register
     StatusReset = 0x6B,
     SoftwareReset = 0x6C,
@@ -39,6 +44,13 @@ pub enum WriteRegister {
     ReservedB9 = 0xB9,
 }
 
+impl WriteRegister {
+    /// Returns the address of the register.
+    pub const fn address(self) -> u8 {
+        self as u8
+    }
+}
+
 impl TryFrom<u8> for WriteRegister {
     type Error = ();
 
@@ -51,8 +63,37 @@ impl TryFrom<u8> for WriteRegister {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromRepr)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ReadRegister {
     Status = 0xA8,
+
+    // Register write protection state (datasheet p.11), 1 = register group unlocked
+    UnlockState = 0xA9,
+
+    // LED fault detection registers, Gen_SHORT and Gen_OPEN (datasheet p.19)
+    ShortFault00To05 = 0xAC,
+    ShortFault06To11 = 0xAD,
+    OpenFault00To05 = 0xAE,
+    OpenFault06To11 = 0xAF,
+}
+
+impl TryFrom<u8> for ReadRegister {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        let reg = ReadRegister::from_repr(value);
+        match reg {
+            Some(reg) => Ok(reg),
+            None => Err(()),
+        }
+    }
+}
+
+impl ReadRegister {
+    /// Returns the address of the register.
+    pub const fn address(self) -> u8 {
+        self as u8
+    }
 }
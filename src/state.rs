@@ -0,0 +1,16 @@
+use crate::CHANNELS_PER_IC;
+
+/// The `DriverState` struct is a snapshot of the state cached by the driver, as returned
+/// by `Bd18378::dump_state()`.
+///
+/// *Note: This is not a live view of the IC state.*
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DriverState {
+    /// Whether the initialization sequence has been successfully executed.
+    pub initialized: bool,
+    /// The pending enabled state of the LED channels, bit 0 corresponds to channel 0.
+    pub channel_mask: u16,
+    /// The calibration value last written to each LED channel, `None` if unknown.
+    pub calibration: [Option<u8>; CHANNELS_PER_IC],
+}
@@ -0,0 +1,138 @@
+/// Bit of the status register indicating a power-on reset, i.e. an undervoltage lockout
+/// or a software reset.
+const POR_BIT: u8 = 0;
+
+/// Bit of the status register indicating a die temperature above 130°C.
+const TSD130_BIT: u8 = 1;
+
+/// Bit of the status register indicating a die temperature above 180°C.
+const TSD180_BIT: u8 = 2;
+
+/// Bit of the status register indicating a weak LED supply.
+const WLS_BIT: u8 = 3;
+
+/// Bit of the status register indicating a fault of the external reference resistor.
+const REXT_BIT: u8 = 4;
+
+/// Bit of the status register indicating a short or open on at least one output.
+const ANY_SHORT_OPEN_BIT: u8 = 5;
+
+/// Bit of the status register indicating a short to ground on at least one output.
+const ANY_SHORT_TO_GND_BIT: u8 = 6;
+
+/// Mask of all status flags indicating a fault.
+const FAULT_MASK: u8 = (1 << TSD130_BIT)
+    | (1 << TSD180_BIT)
+    | (1 << WLS_BIT)
+    | (1 << REXT_BIT)
+    | (1 << ANY_SHORT_OPEN_BIT)
+    | (1 << ANY_SHORT_TO_GND_BIT);
+
+/// The `Status` struct represents the decoded content of the status register
+/// of the BD18378 LED Driver IC.
+///
+/// The status flags are mapped to the register bits as documented in the
+/// "STATUS / UNLOCK Register Flag Description" of the datasheet (p.12):
+/// - Bit 0: `POR_FLAG`, a power-on reset occurred (set after startup)
+/// - Bit 1: `TSD130_FLAG`, die temperature above 130°C
+/// - Bit 2: `TSD180_FLAG`, die temperature above 180°C, the output currents are disabled
+/// - Bit 3: `WLS_FAULT_FLAG`, LED supply voltage below the threshold
+/// - Bit 4: `REXT_FAULT_FLAG`, open or short at the IREF pin
+/// - Bit 5: `ANY_SHORT_OPEN_FLAG`, short or open detected on at least one output
+/// - Bit 6: `ANY_SHORT_TO_GND_FLAG`, short to ground detected on at least one output
+///
+/// The flags stay set until they are cleared by the controller. Bit 7 is unused, but
+/// kept and accessible via `bits()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Status {
+    bits: u8,
+}
+
+impl Status {
+    /// Creates a new `Status` from the raw value of the status register.
+    pub const fn from_bits(bits: u8) -> Self {
+        Status { bits }
+    }
+
+    /// Returns the raw value of the status register.
+    pub const fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Returns whether the IC went through a power-on reset, i.e. an undervoltage
+    /// lockout or a software reset, since the flag was last cleared.
+    pub const fn power_on_reset(&self) -> bool {
+        self.is_set(POR_BIT)
+    }
+
+    /// Returns whether the die temperature exceeds 130°C. The IC keeps working.
+    pub const fn temperature_warning(&self) -> bool {
+        self.is_set(TSD130_BIT)
+    }
+
+    /// Returns whether the die temperature exceeds 180°C, in which case the output
+    /// currents are disabled.
+    pub const fn thermal_shutdown(&self) -> bool {
+        self.is_set(TSD180_BIT)
+    }
+
+    /// Returns whether the IC reports an over-temperature condition, i.e. either the
+    /// temperature warning or the thermal shutdown.
+    pub const fn over_temperature(&self) -> bool {
+        self.temperature_warning() || self.thermal_shutdown()
+    }
+
+    /// Returns whether the LED supply voltage is below the threshold.
+    pub const fn weak_led_supply(&self) -> bool {
+        self.is_set(WLS_BIT)
+    }
+
+    /// Returns whether an open or short was detected at the external reference resistor.
+    pub const fn rext_fault(&self) -> bool {
+        self.is_set(REXT_BIT)
+    }
+
+    /// Returns whether a short or open was detected on at least one output channel.
+    ///
+    /// Use `Bd18378::read_channel_faults()` to find the affected channels.
+    pub const fn short_or_open(&self) -> bool {
+        self.is_set(ANY_SHORT_OPEN_BIT)
+    }
+
+    /// Returns whether a short to ground was detected on at least one output channel.
+    pub const fn short_to_ground(&self) -> bool {
+        self.is_set(ANY_SHORT_TO_GND_BIT)
+    }
+
+    /// Returns whether any flag other than the power-on reset flag is set.
+    pub const fn has_fault(&self) -> bool {
+        self.bits & FAULT_MASK != 0
+    }
+
+    /// Helper function to check a single bit of the status register.
+    const fn is_set(&self, bit: u8) -> bool {
+        self.bits & (1 << bit) != 0
+    }
+}
+
+/// The `ChannelFaults` struct represents the per-channel fault detection state
+/// of the BD18378 LED Driver IC.
+///
+/// Both masks use one bit per LED channel, bit 0 corresponding to channel 0 and
+/// bit 11 to channel 11.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelFaults {
+    /// Channels with an open LED string.
+    pub open: u16,
+    /// Channels with a shorted LED string.
+    pub short: u16,
+}
+
+impl ChannelFaults {
+    /// Returns whether any channel reports a fault.
+    pub const fn any(&self) -> bool {
+        self.open != 0 || self.short != 0
+    }
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "async")]
+
+use bd18378::asynch::Bd18378Async;
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+#[test]
+fn async_chip_init_success() {
+    let expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378Async::new(&mut spi);
+    let result = block_on(bd18378.init());
+
+    assert!(result.is_ok());
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn async_led_activation_no_init() {
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378Async::new(&mut spi);
+    let result = block_on(bd18378.update_all_channels());
+
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn async_led_activation_success() {
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let activation_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0001_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0001_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut activation_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378Async::new(&mut spi);
+    block_on(bd18378.init()).unwrap();
+    bd18378.enable_channel(0).unwrap();
+    bd18378.enable_channel(4).unwrap();
+    bd18378.enable_channel(6).unwrap();
+    bd18378.enable_channel(10).unwrap();
+    let result = block_on(bd18378.update_all_channels());
+    assert!(result.is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn async_led_calibration_success() {
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let calibration_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![bd18378::registers::WriteRegister::ChannelCalibration03 as u8, 0x05u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut calibration_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378Async::new(&mut spi);
+    block_on(bd18378.init()).unwrap();
+    let result = block_on(bd18378.set_channel_calibration(3, 0x05u8));
+    assert!(result.is_ok());
+
+    let result = block_on(bd18378.set_channel_calibration(12, 0x05u8));
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+
+    spi.done();
+}
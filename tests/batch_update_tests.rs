@@ -0,0 +1,85 @@
+use bd18378::Bd18378;
+use bd18378::registers::WriteRegister;
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+#[test]
+fn batch_update_invalid_channel() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.apply_updates(&[(0, true, 0x10u8), (12, true, 0x10u8)]);
+
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+
+    spi.done();
+}
+
+#[test]
+fn batch_update_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.apply_updates(&[(0, true, 0x10u8)]);
+
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn batch_update_writes_only_changed_groups() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let update_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable00To05 as u8, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable06To11 as u8, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration01 as u8, 0x10u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration02 as u8, 0x20u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable00To05 as u8, 0b0000_0010u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut update_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.update_all_channels().unwrap();
+    let result = bd18378.apply_updates(&[(1, true, 0x10u8), (2, false, 0x20u8)]);
+    assert!(result.is_ok());
+
+    spi.done();
+}
@@ -0,0 +1,71 @@
+use bd18378::builder::Bd18378Builder;
+use bd18378::registers::WriteRegister;
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+#[test]
+fn builder_applies_settings() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let settings_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration00 as u8, 0x10u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration08 as u8, 0x2Cu8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable00To05 as u8, 0b0001_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable06To11 as u8, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut settings_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let bd18378 = Bd18378Builder::new(&mut spi)
+        .enable(0)
+        .calibrate(8, 0x2Cu8)
+        .enable(4)
+        .calibrate(0, 0x10u8)
+        .build()
+        .unwrap();
+
+    assert!(bd18378.is_initialized());
+    assert_eq!(bd18378.enabled_channels_mask(), 0b0000_0001_0001);
+
+    spi.done();
+}
+
+#[test]
+fn builder_invalid_channel() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let result = Bd18378Builder::new(&mut spi)
+        .enable(0)
+        .calibrate(12, 0x10u8)
+        .build();
+
+    assert!(matches!(result, Err(bd18378::Error::InvalidChannel)));
+
+    spi.done();
+}
@@ -0,0 +1,168 @@
+use bd18378::chain::Bd18378Chain;
+use bd18378::registers::{ReadRegister, WriteRegister};
+use bd18378::Error;
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+fn chain_transfer(write: Vec<u8>, response: Vec<u8>) -> [Transaction<u8>; 3] {
+    [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(write, response),
+        Transaction::transaction_end(),
+    ]
+}
+
+/// Expectations for initializing a chain of two devices, each device echoing the
+/// frames of the previous transaction.
+fn get_chain_init_expectations() -> Vec<Transaction<u8>> {
+    let mut expectations = Vec::new();
+    let mut previous = vec![0x00, 0x00, 0x00, 0x00];
+    for (reg, value) in bd18378::init_sequence() {
+        let frames = vec![reg as u8, value, reg as u8, value];
+        expectations.extend(chain_transfer(frames.clone(), previous));
+        previous = frames;
+    }
+    expectations.extend(chain_transfer(
+        vec![
+            WriteRegister::StatusReset as u8,
+            0b0011_1111u8,
+            WriteRegister::StatusReset as u8,
+            0b0011_1111u8,
+        ],
+        previous,
+    ));
+    let status_reset = vec![
+        WriteRegister::StatusReset as u8,
+        0b0011_1111u8,
+        WriteRegister::StatusReset as u8,
+        0b0011_1111u8,
+    ];
+    expectations.extend(chain_transfer(
+        vec![ReadRegister::Status as u8, 0x00, ReadRegister::Status as u8, 0x00],
+        status_reset,
+    ));
+    // the status is shifted out during the transaction following the read
+    expectations.extend(chain_transfer(
+        vec![ReadRegister::Status as u8, 0x00, ReadRegister::Status as u8, 0x00],
+        vec![ReadRegister::Status as u8, 0x00, ReadRegister::Status as u8, 0x00],
+    ));
+    expectations
+}
+
+#[test]
+fn chain_init_success() {
+
+    let expectations = get_chain_init_expectations();
+    let mut spi = Mock::new(&expectations);
+
+    let mut chain: Bd18378Chain<_, 2> = Bd18378Chain::new(&mut spi);
+    assert_eq!(chain.init(), Ok(()));
+    assert!(chain.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chain_init_status_fail() {
+
+    let mut expectations = get_chain_init_expectations();
+    // the status of device 1 is shifted out first
+    let len = expectations.len();
+    expectations[len - 2] = Transaction::transfer_in_place(
+        vec![ReadRegister::Status as u8, 0x00, ReadRegister::Status as u8, 0x00],
+        vec![ReadRegister::Status as u8, 0b0000_0001, ReadRegister::Status as u8, 0x00],
+    );
+    let mut spi = Mock::new(&expectations);
+
+    let mut chain: Bd18378Chain<_, 2> = Bd18378Chain::new(&mut spi);
+    assert_eq!(chain.init(), Err(Error::InitFailed));
+    assert!(!chain.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chain_update_shifted_layout() {
+
+    let mut expectations = get_chain_init_expectations();
+    // device 1 is shifted in first, device 0 last
+    expectations.extend(chain_transfer(
+        vec![
+            WriteRegister::ChannelEnable00To05 as u8,
+            0b0000_0001,
+            WriteRegister::ChannelEnable00To05 as u8,
+            0b0000_0100,
+        ],
+        vec![0x00, 0x00, 0x00, 0x00],
+    ));
+    expectations.extend(chain_transfer(
+        vec![
+            WriteRegister::ChannelEnable06To11 as u8,
+            0b0010_0000,
+            WriteRegister::ChannelEnable06To11 as u8,
+            0b0000_0000,
+        ],
+        vec![0x00, 0x00, 0x00, 0x00],
+    ));
+    let mut spi = Mock::new(&expectations);
+
+    let mut chain: Bd18378Chain<_, 2> = Bd18378Chain::new(&mut spi);
+    chain.init().unwrap();
+    chain.enable_channel(2).unwrap();
+    chain.enable_channel(12).unwrap();
+    chain.enable_channel(23).unwrap();
+    assert_eq!(chain.update_all_channels(), Ok(()));
+    assert_eq!(chain.is_channel_enabled(23), Ok(true));
+    assert_eq!(chain.is_channel_enabled(22), Ok(false));
+
+    spi.done();
+}
+
+#[test]
+fn chain_calibration_addresses_single_device() {
+
+    let mut expectations = get_chain_init_expectations();
+    expectations.extend(chain_transfer(
+        vec![
+            WriteRegister::ChannelCalibration03 as u8,
+            0x2A,
+            ReadRegister::Status as u8,
+            0x00,
+        ],
+        vec![0x00, 0x00, 0x00, 0x00],
+    ));
+    let mut spi = Mock::new(&expectations);
+
+    let mut chain: Bd18378Chain<_, 2> = Bd18378Chain::new(&mut spi);
+    chain.init().unwrap();
+    assert_eq!(chain.set_channel_calibration(15, 0x2A), Ok(()));
+
+    spi.done();
+}
+
+#[test]
+fn chain_invalid_channel() {
+
+    let expectations = get_chain_init_expectations();
+    let mut spi = Mock::new(&expectations);
+
+    let mut chain: Bd18378Chain<_, 2> = Bd18378Chain::new(&mut spi);
+    chain.init().unwrap();
+    assert_eq!(chain.enable_channel(24), Err(Error::InvalidChannel));
+    assert_eq!(chain.set_channel_calibration(24, 0x00), Err(Error::InvalidChannel));
+    assert_eq!(Bd18378Chain::<Mock<u8>, 2>::CHANNELS, 24);
+
+    spi.done();
+}
+
+#[test]
+fn chain_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut chain: Bd18378Chain<_, 2> = Bd18378Chain::new(&mut spi);
+    assert_eq!(chain.enable_channel(0), Err(Error::NotInitialized));
+    assert_eq!(chain.update_all_channels(), Err(Error::NotInitialized));
+
+    spi.done();
+}
@@ -0,0 +1,98 @@
+use bd18378::Bd18378;
+use bd18378::channel::{Channel, ChannelGroup};
+use bd18378::registers::WriteRegister;
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+#[test]
+fn channel_enum_to_index() {
+    let channels = [
+        Channel::Ch0,
+        Channel::Ch1,
+        Channel::Ch2,
+        Channel::Ch3,
+        Channel::Ch4,
+        Channel::Ch5,
+        Channel::Ch6,
+        Channel::Ch7,
+        Channel::Ch8,
+        Channel::Ch9,
+        Channel::Ch10,
+        Channel::Ch11,
+    ];
+    for (index, channel) in channels.iter().enumerate() {
+        assert_eq!(usize::from(*channel), index);
+    }
+}
+
+#[test]
+fn channel_enum_and_index_produce_identical_writes() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let channel_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration03 as u8, 0x11u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable00To05 as u8, 0b0000_1000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable06To11 as u8, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    for use_enum in [false, true] {
+        let mut expectations = init_expectations.to_vec();
+        expectations.append(&mut channel_expectations.to_vec());
+        let mut spi = Mock::new(&expectations);
+
+        let mut bd18378 = Bd18378::new(&mut spi);
+        bd18378.init().unwrap();
+        if use_enum {
+            bd18378.set_channel_calibration(Channel::Ch3, 0x11u8).unwrap();
+            bd18378.enable_channel(Channel::Ch3).unwrap();
+            assert_eq!(bd18378.is_channel_enabled(Channel::Ch3), Ok(true));
+        } else {
+            bd18378.set_channel_calibration(3, 0x11u8).unwrap();
+            bd18378.enable_channel(3).unwrap();
+            assert_eq!(bd18378.is_channel_enabled(3), Ok(true));
+        }
+        bd18378.update_all_channels().unwrap();
+
+        spi.done();
+    }
+}
+
+#[test]
+fn channel_count() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let bd18378 = Bd18378::new(&mut spi);
+
+    assert_eq!(bd18378::CHANNEL_COUNT, 12);
+    assert_eq!(bd18378.channel_count(), bd18378::CHANNEL_COUNT);
+    assert_eq!(bd18378.channel_states().count(), bd18378::CHANNEL_COUNT);
+
+    spi.done();
+}
+
+#[test]
+fn channel_group_ranges() {
+
+    assert_eq!(ChannelGroup::Low.channels(), 0..6);
+    assert_eq!(ChannelGroup::High.channels(), 6..12);
+    assert_eq!(ChannelGroup::High.first_channel(), 6);
+}
@@ -1,10 +1,13 @@
 extern crate alloc;
 
 use alloc::vec;
-use bd18378::registers::WriteRegister;
+use bd18378::registers::{ReadRegister, WriteRegister};
 use embedded_hal_mock::eh1::spi::Transaction;
 
-pub fn get_init_sequence_spi_expectations() -> [Transaction<u8>; 48] {
+/// Each SPI transaction of `init()` consists of the transaction start, the transfer and
+/// the transaction end.
+pub fn get_init_sequence_spi_expectations(
+) -> [Transaction<u8>; bd18378::INIT_SPI_TRANSACTION_COUNT * 3] {
     [
         Transaction::transaction_start(),
         Transaction::transfer_in_place(
@@ -99,7 +102,19 @@ pub fn get_init_sequence_spi_expectations() -> [Transaction<u8>; 48] {
         Transaction::transaction_start(),
         Transaction::transfer_in_place(
             vec![WriteRegister::StatusReset as u8, 0x3Fu8],
-            vec![0x00u8, 0x00u8],
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![WriteRegister::StatusReset as u8, 0x3Fu8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, 0x00u8],
         ),
         Transaction::transaction_end(),
     ]
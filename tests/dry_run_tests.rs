@@ -0,0 +1,55 @@
+use bd18378::Bd18378;
+use bd18378::dry_run::DryRunSpi;
+use bd18378::registers::WriteRegister;
+
+#[test]
+fn dry_run_init_and_enable() {
+
+    let mut spi = DryRunSpi::<32>::new();
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(1).unwrap();
+    bd18378.enable_channel(7).unwrap();
+    bd18378.update_all_channels().unwrap();
+    assert_eq!(bd18378.read_status(), Ok(0x00));
+
+    let writes = spi.recorded_writes();
+    // 15 frames of the init sequence, the status reset and both enable registers
+    assert_eq!(writes.len(), 18);
+    assert_eq!(writes[0], [WriteRegister::SoftwareReset as u8, 0b1010_0001u8]);
+    assert_eq!(writes[15], [WriteRegister::StatusReset as u8, 0b0011_1111u8]);
+    assert_eq!(writes[16], [WriteRegister::ChannelEnable00To05 as u8, 0b0000_0010u8]);
+    assert_eq!(writes[17], [WriteRegister::ChannelEnable06To11 as u8, 0b0000_0010u8]);
+}
+
+#[test]
+fn dry_run_clear() {
+
+    let mut spi = DryRunSpi::<32>::new();
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let spi = bd18378.release();
+    spi.clear();
+    assert!(spi.recorded_writes().is_empty());
+
+    let mut bd18378 = Bd18378::new(spi);
+    bd18378.read_status().unwrap_err();
+    bd18378.read_register(bd18378::registers::ReadRegister::Status).unwrap();
+    let spi = bd18378.release();
+
+    // register reads are not recorded
+    assert!(spi.recorded_writes().is_empty());
+}
+
+#[test]
+fn dry_run_capacity() {
+
+    let mut spi = DryRunSpi::<4>::new();
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+
+    assert_eq!(spi.recorded_writes().len(), 4);
+}
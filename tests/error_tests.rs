@@ -0,0 +1,56 @@
+use bd18378::Error;
+use embedded_hal::spi::ErrorKind;
+
+#[test]
+fn error_as_core_error() {
+    let error = Error::NotInitialized;
+    let dyn_error: &dyn core::error::Error = &error;
+
+    assert_eq!(dyn_error.to_string(), "device not initialized");
+    assert!(dyn_error.source().is_none());
+}
+
+#[test]
+fn error_display_messages() {
+    assert_eq!(Error::SpiError(ErrorKind::Other).to_string(), "SPI bus error");
+    assert_eq!(
+        Error::CommunicationError {
+            expected: [0x00, 0x00],
+            received: [0xFF, 0xFF],
+        }
+        .to_string(),
+        "unexpected response from device"
+    );
+    assert_eq!(Error::InitFailed.to_string(), "device initialization failed");
+    assert_eq!(Error::NotInitialized.to_string(), "device not initialized");
+    assert_eq!(Error::InvalidChannel.to_string(), "invalid channel index");
+    assert_eq!(Error::Timeout.to_string(), "operation aborted");
+    assert_eq!(Error::InvalidBin.to_string(), "LED bin not covered by calibration model");
+    assert_eq!(Error::PinError.to_string(), "GPIO pin error");
+    assert_eq!(Error::UnexpectedReset.to_string(), "unexpected device reset");
+    assert_eq!(Error::Interlock.to_string(), "interlock open");
+    assert_eq!(Error::InvalidArgument.to_string(), "argument out of range");
+}
+
+#[test]
+fn error_match_with_wildcard() {
+    let describe = |error: Error| match error {
+        Error::SpiError(_) => "bus",
+        Error::NotInitialized => "init",
+        _ => "other",
+    };
+
+    assert_eq!(describe(Error::SpiError(ErrorKind::Other)), "bus");
+    assert_eq!(describe(Error::NotInitialized), "init");
+    assert_eq!(describe(Error::Timeout), "other");
+}
+
+#[test]
+fn error_display_channel_fault() {
+    assert_eq!(Error::ChannelFault { channel: 3 }.to_string(), "LED channel fault");
+}
+
+#[test]
+fn error_display_fault() {
+    assert_eq!(Error::Fault { status: 0x01 }.to_string(), "device reported a fault");
+}
@@ -2,7 +2,9 @@ extern crate alloc;
 
 use alloc::vec;
 use bd18378::Bd18378;
-use bd18378::registers::WriteRegister;
+use bd18378::config::Config;
+use bd18378::registers::{ReadRegister, WriteRegister};
+use embedded_hal::spi::{Phase, Polarity};
 use embedded_hal_mock::eh1::spi::{Mock, Transaction};
 
 mod common;
@@ -74,3 +76,756 @@ fn chip_init_fail_no_answer_pull_up() {
 
     spi.done();
 }
+
+#[test]
+fn chip_init_aborted() {
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+        ),
+        Transaction::transaction_end(),
+    ];
+    let mut spi = Mock::new(&expectations);
+
+    let mut polls = 0;
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_with_abort(|| {
+        polls += 1;
+        polls > 2
+    });
+
+    assert_eq!(result, Err(bd18378::Error::Timeout));
+    assert!(!bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_not_aborted() {
+    let expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_with_abort(|| false);
+
+    assert!(result.is_ok());
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_probe_present() {
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+        ),
+        Transaction::transaction_end(),
+    ];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.probe_present();
+
+    assert_eq!(result, Ok(true));
+    assert!(!bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_probe_no_answer_pull_up() {
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![0xFF, 0xFF],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![0xFF, 0xFF],
+        ),
+        Transaction::transaction_end(),
+    ];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.probe_present();
+
+    assert_eq!(result, Ok(false));
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_power_good_skips_leading_resets() {
+    use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+
+    let expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&expectations[6..]);
+    let mut power_good = PinMock::new(&[PinTransaction::get(State::High)]);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_with_power_good(&mut power_good);
+
+    assert!(result.is_ok());
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+    power_good.done();
+}
+
+#[test]
+fn chip_init_power_good_low_runs_full_sequence() {
+    use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+
+    let expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&expectations);
+    let mut power_good = PinMock::new(&[PinTransaction::get(State::Low)]);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_with_power_good(&mut power_good);
+
+    assert!(result.is_ok());
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+    power_good.done();
+}
+
+#[test]
+fn chip_init_fail_unexpected_reset() {
+    let mut expectations = common::get_init_sequence_spi_expectations()[..9].to_vec();
+    expectations.append(&mut vec![
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ReservedB6 as u8, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ]);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init();
+
+    assert_eq!(result, Err(bd18378::Error::UnexpectedReset));
+    assert!(!bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_fail_stale_echo() {
+    // the device answers with a frame sent before the expected one
+    let mut expectations = common::get_init_sequence_spi_expectations()[..12].to_vec();
+    expectations.append(&mut vec![
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ReservedB5 as u8, 0b1001_1110u8],
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+        ),
+        Transaction::transaction_end(),
+    ]);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init();
+
+    assert_eq!(result, Err(bd18378::Error::UnexpectedReset));
+    assert!(!bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_timed() {
+    use core::cell::Cell;
+
+    let expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&expectations);
+
+    let ticks = Cell::new(u64::MAX - 1);
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_timed(|| {
+        let now = ticks.get();
+        ticks.set(now.wrapping_add(5));
+        now
+    });
+
+    assert_eq!(result, Ok(5));
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_echo_depth_two() {
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let frames = [
+        [WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+        [WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+        [WriteRegister::ReservedB5 as u8, 0b1001_1110u8],
+        [WriteRegister::ReservedB6 as u8, 0b0000_0000u8],
+        [WriteRegister::ReservedB5 as u8, 0b1001_1110u8],
+        [WriteRegister::ReservedB7 as u8, 0b0000_0000u8],
+        [WriteRegister::ReservedB5 as u8, 0b1001_1110u8],
+        [WriteRegister::ReservedB8 as u8, 0b0000_0000u8],
+        [WriteRegister::ReservedB5 as u8, 0b1001_1110u8],
+        [WriteRegister::ReservedB9 as u8, 0b0000_0000u8],
+        [WriteRegister::Reserved79 as u8, 0b1101_0110u8],
+        [WriteRegister::Reserved7A as u8, 0b0000_0000u8],
+        [WriteRegister::Reserved79 as u8, 0b1101_0110u8],
+        [WriteRegister::Reserved7B as u8, 0b0000_0000u8],
+        [WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+    ];
+
+    let mut expectations = Vec::new();
+    for (i, frame) in frames.iter().enumerate() {
+        let response = if i >= 2 { frames[i - 2] } else { [0x00, 0x00] };
+        expectations.push(Transaction::transaction_start());
+        expectations.push(Transaction::transfer_in_place(frame.to_vec(), response.to_vec()));
+        expectations.push(Transaction::transaction_end());
+    }
+    expectations.extend_from_slice(&init_expectations[45..]);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.set_echo_depth(2).unwrap();
+    let result = bd18378.init();
+
+    assert!(result.is_ok());
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_echo_depth_invalid() {
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+
+    assert_eq!(bd18378.set_echo_depth(0), Err(bd18378::Error::InvalidArgument));
+    assert_eq!(bd18378.set_echo_depth(5), Err(bd18378::Error::InvalidArgument));
+
+    spi.done();
+}
+
+#[test]
+fn chip_release_spi() {
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut expectations = init_expectations.to_vec();
+    expectations.extend_from_slice(&init_expectations);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let spi_ref = bd18378.release();
+
+    let mut bd18378 = Bd18378::new(spi_ref);
+    assert!(!bd18378.is_initialized());
+    bd18378.init().unwrap();
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_new_initialized_success() {
+    let expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&expectations);
+
+    let bd18378 = Bd18378::new_initialized(&mut spi).unwrap();
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_new_initialized_fail() {
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![0xFF, 0xFF],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![0xFF, 0xFF],
+        ),
+        Transaction::transaction_end(),
+    ];
+    let mut spi = Mock::new(&expectations);
+
+    let result = Bd18378::new_initialized(&mut spi);
+    assert_eq!(
+        result.err(),
+        Some(bd18378::Error::CommunicationError {
+            expected: [WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            received: [0xFF, 0xFF],
+        })
+    );
+
+    spi.done();
+}
+
+/// Expectations for the initialization sequence, reading back `status` at the end.
+fn get_init_expectations_with_status(status: u8) -> Vec<Transaction<u8>> {
+    let mut expectations = common::get_init_sequence_spi_expectations()[..48].to_vec();
+    expectations.append(&mut vec![
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![WriteRegister::StatusReset as u8, 0x3Fu8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, status],
+        ),
+        Transaction::transaction_end(),
+    ]);
+    expectations
+}
+
+#[test]
+fn chip_init_fail_power_on_reset_flag() {
+    let expectations = get_init_expectations_with_status(0b0000_0001u8);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init();
+
+    assert_eq!(result, Err(bd18378::Error::InitFailed));
+    assert!(!bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_ignores_fault_flags() {
+    // e.g. an open LED string, which is detected again right after the status reset
+    let expectations = get_init_expectations_with_status(0b0010_0000u8);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init();
+
+    assert_eq!(result, Ok(()));
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_reset_clears_state() {
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let reset_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::StatusReset as u8, 0x3Fu8],
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+        ),
+        Transaction::transaction_end(),
+    ];
+    let mut expectations = init_expectations.to_vec();
+    expectations.extend_from_slice(&reset_expectations);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(3).unwrap();
+    bd18378.enable_channel(9).unwrap();
+
+    let result = bd18378.reset();
+
+    assert!(result.is_ok());
+    assert!(!bd18378.is_initialized());
+    assert_eq!(bd18378.enabled_channels_mask(), 0);
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_custom_sequence() {
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ReservedB5 as u8, 0b1001_1110u8],
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ReservedB6 as u8, 0b0000_0011u8],
+            vec![WriteRegister::ReservedB5 as u8, 0b1001_1110u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::StatusReset as u8, 0x3Fu8],
+            vec![WriteRegister::ReservedB6 as u8, 0b0000_0011u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![WriteRegister::StatusReset as u8, 0x3Fu8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, 0x00u8],
+        ),
+        Transaction::transaction_end(),
+    ];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_with_sequence(&[
+        (WriteRegister::SoftwareReset, 0b1010_0001u8),
+        (WriteRegister::ReservedB5, 0b1001_1110u8),
+        (WriteRegister::ReservedB6, 0b0000_0011u8),
+    ]);
+
+    assert!(result.is_ok());
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_fail_reports_mismatch() {
+    let mut expectations = common::get_init_sequence_spi_expectations()[..9].to_vec();
+    expectations.append(&mut vec![
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ReservedB6 as u8, 0b0000_0000u8],
+            vec![WriteRegister::ReservedB5 as u8, 0b1001_1111u8],
+        ),
+        Transaction::transaction_end(),
+    ]);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init();
+
+    assert_eq!(
+        result,
+        Err(bd18378::Error::CommunicationError {
+            expected: [WriteRegister::ReservedB5 as u8, 0b1001_1110u8],
+            received: [WriteRegister::ReservedB5 as u8, 0b1001_1111u8],
+        })
+    );
+    assert!(!bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_with_config() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let config = Config {
+        channel_mask: 0b1100_0000_0011,
+        calibration: [
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B,
+        ],
+    };
+
+    let mut expectations = init_expectations.to_vec();
+    for (ch, value) in config.calibration.iter().enumerate() {
+        expectations.push(Transaction::transaction_start());
+        expectations.push(Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration00 as u8 + ch as u8, *value],
+            vec![0x00, 0x00],
+        ));
+        expectations.push(Transaction::transaction_end());
+    }
+    expectations.push(Transaction::transaction_start());
+    expectations.push(Transaction::transfer_in_place(
+        vec![WriteRegister::ChannelEnable00To05 as u8, 0b0000_0011u8],
+        vec![0x00, 0x00],
+    ));
+    expectations.push(Transaction::transaction_end());
+    expectations.push(Transaction::transaction_start());
+    expectations.push(Transaction::transfer_in_place(
+        vec![WriteRegister::ChannelEnable06To11 as u8, 0b0011_0000u8],
+        vec![0x00, 0x00],
+    ));
+    expectations.push(Transaction::transaction_end());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_with_config(&config);
+
+    assert_eq!(result, Ok(()));
+    assert!(bd18378.is_initialized());
+    assert_eq!(bd18378.enabled_channels_mask(), config.channel_mask);
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_with_config_invalid_mask() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let config = Config {
+        channel_mask: 0b0001_0000_0000_0000,
+        calibration: [0x00; 12],
+    };
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_with_config(&config);
+
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+    assert!(!bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn expected_spi_mode() {
+
+    let mode = bd18378::expected_spi_mode();
+
+    assert_eq!(mode.polarity, Polarity::IdleLow);
+    assert_eq!(mode.phase, Phase::CaptureOnFirstTransition);
+}
+
+/// Expectations for an initialization attempt failing with a mismatching echo at the
+/// fourth transaction.
+fn get_failing_init_attempt_expectations() -> Vec<Transaction<u8>> {
+    let mut expectations = common::get_init_sequence_spi_expectations()[..9].to_vec();
+    expectations.append(&mut vec![
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ReservedB6 as u8, 0b0000_0000u8],
+            vec![WriteRegister::ReservedB5 as u8, 0b1001_1111u8],
+        ),
+        Transaction::transaction_end(),
+    ]);
+    expectations
+}
+
+#[test]
+fn chip_init_with_retries_recovers() {
+
+    let mut expectations = get_failing_init_attempt_expectations();
+    expectations.append(&mut common::get_init_sequence_spi_expectations().to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_with_retries(3);
+
+    assert_eq!(result, Ok(()));
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_with_retries_exhausted() {
+
+    let mut expectations = get_failing_init_attempt_expectations();
+    expectations.append(&mut get_failing_init_attempt_expectations());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_with_retries(2);
+
+    assert!(matches!(result, Err(bd18378::Error::CommunicationError { .. })));
+    assert!(!bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_with_zero_retries() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_with_retries(0);
+
+    assert_eq!(result, Err(bd18378::Error::InvalidArgument));
+
+    spi.done();
+}
+
+/// Expectations for the initialization sequence of a board which answers every frame
+/// with zeros instead of the echo of the previous frame.
+fn get_non_echoing_init_expectations() -> Vec<Transaction<u8>> {
+    let mut frames = bd18378::init_sequence().to_vec();
+    frames.push((WriteRegister::StatusReset, 0b0011_1111u8));
+    let mut expectations = Vec::new();
+    for (register, value) in frames {
+        expectations.push(Transaction::transaction_start());
+        expectations.push(Transaction::transfer_in_place(
+            vec![register as u8, value],
+            vec![0x00, 0x00],
+        ));
+        expectations.push(Transaction::transaction_end());
+    }
+    expectations.push(Transaction::transaction_start());
+    expectations.push(Transaction::transfer_in_place(
+        vec![ReadRegister::Status as u8, 0x00u8],
+        vec![0x00, 0x00],
+    ));
+    expectations.push(Transaction::transaction_end());
+    // register reads are answered during the following frame nonetheless
+    expectations.push(Transaction::transaction_start());
+    expectations.push(Transaction::transfer_in_place(
+        vec![ReadRegister::Status as u8, 0x00u8],
+        vec![ReadRegister::Status as u8, 0x00u8],
+    ));
+    expectations.push(Transaction::transaction_end());
+    expectations
+}
+
+#[test]
+fn chip_init_strict_fails_without_echo() {
+
+    let mut expectations = get_non_echoing_init_expectations();
+    expectations.truncate(6);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init();
+
+    assert!(matches!(result, Err(bd18378::Error::CommunicationError { .. })));
+
+    spi.done();
+}
+
+#[test]
+fn chip_init_lenient_without_echo() {
+
+    let expectations = get_non_echoing_init_expectations();
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.init_lenient();
+
+    assert_eq!(result, Ok(()));
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn public_init_sequence() {
+
+    let sequence = bd18378::init_sequence();
+
+    assert_eq!(sequence.len(), 15);
+    assert_eq!(sequence[0], (WriteRegister::SoftwareReset, 0b1010_0001u8));
+    assert_eq!(sequence[14], (WriteRegister::SoftwareReset, 0b1010_0001u8));
+}
+
+#[test]
+fn init_sequence_lengths() {
+
+    assert_eq!(bd18378::INIT_SEQUENCE_LEN, 15);
+    assert_eq!(bd18378::INIT_SPI_TRANSACTION_COUNT, 18);
+    assert_eq!(
+        common::get_init_sequence_spi_expectations().len(),
+        bd18378::INIT_SPI_TRANSACTION_COUNT * 3
+    );
+}
+
+#[test]
+fn chip_reinit_preserving_channels() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let state_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration10 as u8, 0x33u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable00To05 as u8, 0b0000_1000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable06To11 as u8, 0b0001_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    // the same writes are expected before and after the re-initialization
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut state_expectations.to_vec());
+    expectations.append(&mut init_expectations.to_vec());
+    expectations.append(&mut state_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.set_channel_calibration(10, 0x33u8).unwrap();
+    bd18378.enable_channel(3).unwrap();
+    bd18378.enable_channel(10).unwrap();
+    bd18378.update_all_channels().unwrap();
+
+    let result = bd18378.reinit_preserving_channels();
+
+    assert_eq!(result, Ok(()));
+    assert!(bd18378.is_initialized());
+    assert_eq!(bd18378.enabled_channels_mask(), 0b0100_0000_1000);
+    assert_eq!(bd18378.get_channel_calibration(10), Ok(Some(0x33u8)));
+
+    spi.done();
+}
@@ -0,0 +1,80 @@
+use bd18378::Bd18378;
+use bd18378::interlock::InterlockPin;
+use embedded_hal::digital::PinState;
+use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+#[test]
+fn interlock_closed_updates_channels() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let activation_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut activation_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+    let mut pin = PinMock::new(&[PinTransaction::get(State::Low)]);
+
+    let mut bd18378 =
+        Bd18378::new_with_interlock(&mut spi, InterlockPin::new(&mut pin, PinState::High));
+    bd18378.init().unwrap();
+    bd18378.enable_channel(0).unwrap();
+    let result = bd18378.update_all_channels();
+    assert!(result.is_ok());
+
+    spi.done();
+    pin.done();
+}
+
+#[test]
+fn interlock_open_disables_all_channels() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let interlock_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut interlock_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+    let mut pin = PinMock::new(&[PinTransaction::get(State::High)]);
+
+    let mut bd18378 =
+        Bd18378::new_with_interlock(&mut spi, InterlockPin::new(&mut pin, PinState::High));
+    bd18378.init().unwrap();
+    bd18378.enable_channel(0).unwrap();
+    bd18378.enable_channel(6).unwrap();
+    let result = bd18378.update_all_channels();
+    assert_eq!(result, Err(bd18378::Error::Interlock));
+
+    spi.done();
+    pin.done();
+}
@@ -1,4 +1,5 @@
-use bd18378::Bd18378;
+use bd18378::channel::ChannelGroup;
+use bd18378::{Bd18378, UpdateOrder};
 use embedded_hal_mock::eh1::spi::{Mock, Transaction};
 
 mod common;
@@ -56,7 +57,7 @@ fn led_activation_success() {
     
     let mut expectations = init_expectations.to_vec();
     expectations.append(&mut activation_expectations.to_vec());
-    let expectations: [_; 54] = expectations.try_into().unwrap();
+    let expectations: [_; 60] = expectations.try_into().unwrap();
     let mut spi = Mock::new(&expectations);
 
     let mut bd18378 = Bd18378::new(&mut spi);
@@ -128,7 +129,7 @@ fn led_deactivation_success() {
     
     let mut expectations = init_expectations.to_vec();
     expectations.append(&mut deactivation_expectations.to_vec());
-    let expectations: [_; 54] = expectations.try_into().unwrap();
+    let expectations: [_; 60] = expectations.try_into().unwrap();
     let mut spi = Mock::new(&expectations);
 
     let mut bd18378 = Bd18378::new(&mut spi);
@@ -145,4 +146,722 @@ fn led_deactivation_success() {
     assert!(result.is_ok());
 
     spi.done();
-}
\ No newline at end of file
+}
+
+#[test]
+fn led_mask_or_and_clear_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let mask_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0011u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0010_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0010u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut mask_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(0).unwrap();
+    let result = bd18378.or_channels_mask(0b1000_0000_0010);
+    assert!(result.is_ok());
+    let result = bd18378.clear_channels_mask(0b0000_0000_0101);
+    assert!(result.is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn led_mask_or_invalid_channel() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.or_channels_mask(0b0001_0000_0000_0001);
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+    let result = bd18378.clear_channels_mask(0b1000_0000_0000_0000);
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+
+    spi.done();
+}
+
+#[test]
+fn led_write_channel_mask_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let mask_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0001_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0100u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut mask_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.write_channel_mask(0b0001_0001_0001);
+    assert_eq!(result, Ok(()));
+    assert_eq!(bd18378.enabled_channels_mask(), 0b0001_0001_0001);
+
+    spi.done();
+}
+
+#[test]
+fn led_write_channel_mask_invalid_channel() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(3).unwrap();
+    let result = bd18378.write_channel_mask(0b0010_0000_0000_0001);
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+    assert_eq!(bd18378.enabled_channels_mask(), 0b0000_0000_1000);
+
+    spi.done();
+}
+
+#[test]
+fn led_set_group_updates_only_its_register() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let group_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0101u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0010_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut group_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    // the upper 2 bits are ignored
+    bd18378.set_group(ChannelGroup::Low, 0b1100_0101).unwrap();
+    bd18378.update_all_channels().unwrap();
+    bd18378.set_group(ChannelGroup::High, 0b0010_0001).unwrap();
+    bd18378.update_all_channels().unwrap();
+    assert_eq!(bd18378.enabled_channels_mask(), 0b1000_0100_0101);
+    bd18378.set_group(ChannelGroup::Low, 0).unwrap();
+    bd18378.update_all_channels().unwrap();
+    assert_eq!(bd18378.enabled_channels_mask(), 0b1000_0100_0000);
+
+    spi.done();
+}
+
+#[test]
+fn led_set_group_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.set_group(ChannelGroup::High, 0b0000_0001);
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn led_update_report_written_groups() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let update_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0010u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut update_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(0).unwrap();
+    assert_eq!(bd18378.update_all_channels_report(), Ok(0x0FFF));
+    bd18378.enable_channel(7).unwrap();
+    assert_eq!(bd18378.update_all_channels_report(), Ok(0x0FC0));
+    assert_eq!(bd18378.update_all_channels_report(), Ok(0x0000));
+
+    spi.done();
+}
+
+#[test]
+fn led_enable_channel_and_update_single_group() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let update_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0100u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_1000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut update_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    assert_eq!(bd18378.enable_channel_and_update(2), Ok(()));
+    assert_eq!(bd18378.enable_channel_and_update(9), Ok(()));
+    // unchanged, nothing is written
+    assert_eq!(bd18378.enable_channel_and_update(9), Ok(()));
+    assert_eq!(bd18378.disable_channel_and_update(2), Ok(()));
+    assert_eq!(bd18378.enabled_channels_mask(), 0b0010_0000_0000);
+
+    spi.done();
+}
+
+#[test]
+fn led_enable_channel_and_update_invalid_channel() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    assert_eq!(bd18378.enable_channel_and_update(12), Err(bd18378::Error::InvalidChannel));
+    assert_eq!(bd18378.disable_channel_and_update(12), Err(bd18378::Error::InvalidChannel));
+
+    spi.done();
+}
+
+#[test]
+fn led_computed_enable_registers() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    assert_eq!(bd18378.computed_enable_registers(), (0x00, 0x00));
+    bd18378.set_channels(0b1010_0110_0001).unwrap();
+    assert_eq!(bd18378.computed_enable_registers(), (0b0010_0001, 0b0010_1001));
+
+    spi.done();
+}
+
+#[test]
+fn led_update_ordered_off_first() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let update_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut update_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(6).unwrap();
+    bd18378.update_all_channels().unwrap();
+    bd18378.disable_channel(6).unwrap();
+    bd18378.enable_channel(0).unwrap();
+    let result = bd18378.update_all_channels_ordered(UpdateOrder::OffFirst);
+    assert!(result.is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn led_update_ordered_on_first() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let update_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut update_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(0).unwrap();
+    bd18378.update_all_channels().unwrap();
+    bd18378.disable_channel(0).unwrap();
+    bd18378.enable_channel(6).unwrap();
+    let result = bd18378.update_all_channels_ordered(UpdateOrder::OnFirst);
+    assert!(result.is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn led_activation_all_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    assert_eq!(bd18378.enable_all_channels(), Err(bd18378::Error::NotInitialized));
+    assert_eq!(bd18378.disable_all_channels(), Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn led_activation_all_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let activation_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0011_1111u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0011_1111u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut activation_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    assert!(bd18378.enable_all_channels().is_ok());
+    assert!(bd18378.update_all_channels().is_ok());
+    assert!(bd18378.disable_all_channels().is_ok());
+    assert!(bd18378.update_all_channels().is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn led_toggle_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.toggle_channel(0);
+
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn led_toggle_invalid_channel() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.toggle_channel(12);
+
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+
+    spi.done();
+}
+
+#[test]
+fn led_toggle_twice_restores_state() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let toggle_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_1000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut toggle_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    assert!(bd18378.toggle_channel(3).is_ok());
+    assert!(bd18378.update_all_channels().is_ok());
+    assert!(bd18378.toggle_channel(3).is_ok());
+    assert!(bd18378.update_all_channels().is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn led_is_channel_enabled() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    assert_eq!(bd18378.is_channel_enabled(5), Ok(false));
+    bd18378.enable_channel(5).unwrap();
+    assert_eq!(bd18378.is_channel_enabled(5), Ok(true));
+    assert_eq!(bd18378.is_channel_enabled(4), Ok(false));
+    assert_eq!(bd18378.is_channel_enabled(12), Err(bd18378::Error::InvalidChannel));
+
+    spi.done();
+}
+
+#[test]
+fn led_set_channels_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.set_channels(0b0000_0000_0001);
+
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn led_set_channels_invalid_channel() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.set_channels(0b0010_0000_0000_0000);
+
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+
+    spi.done();
+}
+
+#[test]
+fn led_set_channels_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let activation_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0010_1010u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0010_1010u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut activation_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    assert!(bd18378.set_channels(0b1010_1010_1010).is_ok());
+    assert!(bd18378.update_all_channels().is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn led_enabled_channels_mask() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    assert_eq!(bd18378.enabled_channels_mask(), 0);
+    bd18378.enable_channel(0).unwrap();
+    bd18378.enable_channel(7).unwrap();
+    bd18378.enable_channel(11).unwrap();
+    assert_eq!(bd18378.enabled_channels_mask(), 0b1000_1000_0001);
+    bd18378.set_channels(0b0101_0000_1010).unwrap();
+    assert_eq!(bd18378.enabled_channels_mask(), 0b0101_0000_1010);
+
+    spi.done();
+}
+
+#[test]
+fn led_channel_states() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(1).unwrap();
+    bd18378.enable_channel(10).unwrap();
+
+    let states: Vec<(usize, bool)> = bd18378.channel_states().collect();
+    assert_eq!(states.len(), 12);
+    assert_eq!(states[0], (0, false));
+    assert_eq!(states[1], (1, true));
+    assert_eq!(states[10], (10, true));
+    assert_eq!(states[11], (11, false));
+
+    let enabled: Vec<usize> = bd18378
+        .channel_states()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(ch, _)| ch)
+        .collect();
+    assert_eq!(enabled, vec![1, 10]);
+
+    spi.done();
+}
+
+#[test]
+fn led_update_skips_unchanged_registers() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let activation_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut activation_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(0).unwrap();
+    assert!(bd18378.update_all_channels().is_ok());
+    // nothing changed, no SPI transactions expected
+    assert!(bd18378.update_all_channels().is_ok());
+    bd18378.enable_channel(6).unwrap();
+    assert!(bd18378.update_all_channels().is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn led_enable_channels_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.enable_channels(&[0, 1]);
+
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn led_enable_channels_invalid_channel() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.enable_channels(&[0, 5, 12]);
+
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+    assert_eq!(bd18378.enabled_channels_mask(), 0);
+
+    spi.done();
+}
+
+#[test]
+fn led_enable_channels_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.enable_channels(&[0, 5, 11]);
+
+    assert!(result.is_ok());
+    assert_eq!(bd18378.enabled_channels_mask(), 0b1000_0010_0001);
+
+    spi.done();
+}
@@ -0,0 +1,72 @@
+use bd18378::Bd18378;
+use bd18378::calibration::{CalibrationModel, LedBin};
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+const MODEL_CODES: [u8; 3] = [0x30, 0x20, 0x10];
+
+#[test]
+fn led_bin_calibration_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let model = CalibrationModel::new(&MODEL_CODES);
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.apply_bin_table(&[LedBin(0); 12], &model);
+
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), bd18378::Error::NotInitialized);
+
+    spi.done();
+}
+
+#[test]
+fn led_bin_calibration_invalid_bin() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let model = CalibrationModel::new(&MODEL_CODES);
+    let mut bins = [LedBin(1); 12];
+    bins[11] = LedBin(3);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.apply_bin_table(&bins, &model);
+
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), bd18378::Error::InvalidBin);
+
+    spi.done();
+}
+
+#[test]
+fn led_bin_calibration_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let bins = [0u8, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2].map(LedBin);
+    let mut expectations = init_expectations.to_vec();
+    for (ch, bin) in bins.iter().enumerate() {
+        expectations.push(Transaction::transaction_start());
+        expectations.push(Transaction::transfer_in_place(
+            vec![
+                bd18378::registers::WriteRegister::ChannelCalibration00 as u8 + ch as u8,
+                MODEL_CODES[bin.0 as usize],
+            ],
+            vec![0x00, 0x00],
+        ));
+        expectations.push(Transaction::transaction_end());
+    }
+    let mut spi = Mock::new(&expectations);
+
+    let model = CalibrationModel::new(&MODEL_CODES);
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.apply_bin_table(&bins, &model);
+    assert!(result.is_ok());
+
+    spi.done();
+}
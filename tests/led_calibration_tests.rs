@@ -50,7 +50,7 @@ fn led_calibration_success() {
 
     let mut expectations = init_expectations.to_vec();
     expectations.append(&mut calibration_expectations.to_vec());
-    let expectations: [_; 51] = expectations.try_into().unwrap();
+    let expectations: [_; 57] = expectations.try_into().unwrap();
     let mut spi = Mock::new(&expectations);
 
     let mut bd18378 = Bd18378::new(&mut spi);
@@ -59,4 +59,343 @@ fn led_calibration_success() {
     assert!(result.is_ok());
 
     spi.done();
-}
\ No newline at end of file
+}
+
+#[test]
+fn led_calibration_gradient_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.set_calibration_gradient(0x00u8, 0x3Fu8);
+
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), bd18378::Error::NotInitialized);
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_gradient_out_of_range() {
+
+    let expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+
+    assert_eq!(
+        bd18378.set_calibration_gradient(0x00u8, 0x40u8),
+        Err(bd18378::Error::InvalidArgument)
+    );
+    assert_eq!(
+        bd18378.set_calibration_gradient(0x80u8, 0x3Fu8),
+        Err(bd18378::Error::InvalidArgument)
+    );
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_gradient_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let codes = [0u8, 6, 11, 17, 23, 29, 34, 40, 46, 52, 57, 63];
+    let mut expectations = init_expectations.to_vec();
+    for (ch, code) in codes.iter().enumerate() {
+        expectations.push(Transaction::transaction_start());
+        expectations.push(Transaction::transfer_in_place(
+            vec![bd18378::registers::WriteRegister::ChannelCalibration00 as u8 + ch as u8, *code],
+            vec![0x00, 0x00],
+        ));
+        expectations.push(Transaction::transaction_end());
+    }
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.set_calibration_gradient(0x00u8, 0x3Fu8);
+    assert!(result.is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_gradient_descending() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let codes = [63u8, 57, 52, 46, 40, 34, 29, 23, 17, 11, 6, 0];
+    let mut expectations = init_expectations.to_vec();
+    for (ch, code) in codes.iter().enumerate() {
+        expectations.push(Transaction::transaction_start());
+        expectations.push(Transaction::transfer_in_place(
+            vec![bd18378::registers::WriteRegister::ChannelCalibration00 as u8 + ch as u8, *code],
+            vec![0x00, 0x00],
+        ));
+        expectations.push(Transaction::transaction_end());
+    }
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.set_calibration_gradient(0x3Fu8, 0x00u8);
+    assert!(result.is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_unchanged_value_skipped() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let calibration_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![bd18378::registers::WriteRegister::ChannelCalibration00 as u8, 0x05u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![bd18378::registers::WriteRegister::ChannelCalibration00 as u8, 0x06u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut calibration_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    assert!(bd18378.set_channel_calibration(0, 0x05u8).is_ok());
+    assert!(bd18378.set_channel_calibration(0, 0x05u8).is_ok());
+    assert!(bd18378.set_channel_calibration(0, 0x06u8).is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_all_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let calibration = [
+        0x00u8, 0x01u8, 0x02u8, 0x03u8, 0x04u8, 0x05u8, 0x3Au8, 0x3Bu8, 0x3Cu8, 0x3Du8, 0x3Eu8,
+        0x3Fu8,
+    ];
+    let registers = [
+        bd18378::registers::WriteRegister::ChannelCalibration00,
+        bd18378::registers::WriteRegister::ChannelCalibration01,
+        bd18378::registers::WriteRegister::ChannelCalibration02,
+        bd18378::registers::WriteRegister::ChannelCalibration03,
+        bd18378::registers::WriteRegister::ChannelCalibration04,
+        bd18378::registers::WriteRegister::ChannelCalibration05,
+        bd18378::registers::WriteRegister::ChannelCalibration06,
+        bd18378::registers::WriteRegister::ChannelCalibration07,
+        bd18378::registers::WriteRegister::ChannelCalibration08,
+        bd18378::registers::WriteRegister::ChannelCalibration09,
+        bd18378::registers::WriteRegister::ChannelCalibration10,
+        bd18378::registers::WriteRegister::ChannelCalibration11,
+    ];
+    let mut expectations = init_expectations.to_vec();
+    for (register, value) in registers.iter().zip(calibration.iter()) {
+        expectations.push(Transaction::transaction_start());
+        expectations.push(Transaction::transfer_in_place(
+            vec![*register as u8, *value],
+            vec![0x00, 0x00],
+        ));
+        expectations.push(Transaction::transaction_end());
+    }
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.set_all_channel_calibration(&calibration);
+    assert!(result.is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_all_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.set_all_channel_calibration(&[0x05u8; 12]);
+
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_get_cached_value() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let calibration_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![bd18378::registers::WriteRegister::ChannelCalibration04 as u8, 0x2Au8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut calibration_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    assert_eq!(bd18378.get_channel_calibration(4), Ok(None));
+    bd18378.set_channel_calibration(4, 0x2Au8).unwrap();
+    assert_eq!(bd18378.get_channel_calibration(4), Ok(Some(0x2Au8)));
+    assert_eq!(bd18378.get_channel_calibration(5), Ok(None));
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_get_invalid_channel() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.get_channel_calibration(12);
+
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_range_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let registers = [
+        bd18378::registers::WriteRegister::ChannelCalibration03,
+        bd18378::registers::WriteRegister::ChannelCalibration04,
+        bd18378::registers::WriteRegister::ChannelCalibration05,
+        bd18378::registers::WriteRegister::ChannelCalibration06,
+    ];
+    let mut expectations = init_expectations.to_vec();
+    for register in registers.iter() {
+        expectations.push(Transaction::transaction_start());
+        expectations.push(Transaction::transfer_in_place(
+            vec![*register as u8, 0x2Au8],
+            vec![0x00, 0x00],
+        ));
+        expectations.push(Transaction::transaction_end());
+    }
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.set_channel_calibration_range(3, 7, 0x2Au8);
+    assert_eq!(result, Ok(()));
+    assert_eq!(bd18378.get_channel_calibration(2), Ok(None));
+    assert_eq!(bd18378.get_channel_calibration(6), Ok(Some(0x2Au8)));
+    assert_eq!(bd18378.get_channel_calibration(7), Ok(None));
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_range_inverted() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.set_channel_calibration_range(7, 3, 0x2Au8);
+
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_range_out_of_bounds() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.set_channel_calibration_range(10, 13, 0x2Au8);
+
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+
+    spi.done();
+}
+
+fn calibration_write(register: bd18378::registers::WriteRegister, value: u8) -> [Transaction<u8>; 3] {
+    [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(vec![register as u8, value], vec![0x00, 0x00]),
+        Transaction::transaction_end(),
+    ]
+}
+
+#[test]
+fn led_calibration_clamped() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.extend(calibration_write(
+        bd18378::registers::WriteRegister::ChannelCalibration00,
+        0x12u8,
+    ));
+    expectations.extend(calibration_write(
+        bd18378::registers::WriteRegister::ChannelCalibration01,
+        0x3Fu8,
+    ));
+    expectations.extend(calibration_write(
+        bd18378::registers::WriteRegister::ChannelCalibration02,
+        0x3Fu8,
+    ));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    // in range
+    assert_eq!(bd18378.set_channel_calibration_clamped(0, 0x12u8), Ok(false));
+    // boundary
+    assert_eq!(
+        bd18378.set_channel_calibration_clamped(1, bd18378::MAX_CALIBRATION),
+        Ok(false)
+    );
+    // over range
+    assert_eq!(bd18378.set_channel_calibration_clamped(2, 0x80u8), Ok(true));
+    assert_eq!(bd18378.get_channel_calibration(2), Ok(Some(0x3Fu8)));
+
+    spi.done();
+}
+
+#[test]
+fn led_calibration_clamped_invalid_channel() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.set_channel_calibration_clamped(12, 0xFFu8);
+
+    assert_eq!(result, Err(bd18378::Error::InvalidChannel));
+
+    spi.done();
+}
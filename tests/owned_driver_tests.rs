@@ -0,0 +1,77 @@
+use bd18378::dry_run::DryRunSpi;
+use bd18378::interlock::NoInterlock;
+use bd18378::registers::WriteRegister;
+use bd18378::{Bd18378, Bd18378Owned};
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+/// An application struct storing the driver without a lifetime parameter.
+struct Lights {
+    driver: Bd18378Owned<Mock<u8>>,
+}
+
+impl Lights {
+    fn turn_on(&mut self, ch: usize) -> bd18378::OperationResult {
+        self.driver.enable_channel(ch)?;
+        self.driver.update_all_channels()
+    }
+}
+
+#[test]
+fn owned_driver_in_struct() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let activation_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0000_1000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut activation_expectations.to_vec());
+    let spi = Mock::new(&expectations);
+
+    let mut lights = Lights {
+        driver: Bd18378Owned::new_owned(spi),
+    };
+    lights.driver.init().unwrap();
+    assert_eq!(lights.turn_on(9), Ok(()));
+    assert!(lights.driver.is_channel_enabled(9).unwrap());
+
+    let mut spi = lights.driver.into_spi();
+    spi.done();
+}
+
+#[test]
+fn owned_driver_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378Owned::new_owned(spi);
+    assert_eq!(bd18378.enable_channel(0), Err(bd18378::Error::NotInitialized));
+
+    let mut spi = bd18378.into_spi();
+    spi.done();
+}
+
+fn assert_send<T: Send>() {}
+
+#[test]
+fn driver_is_send() {
+    // checked at compile time, e.g. to move the driver into a thread or an executor task
+    assert_send::<Bd18378Owned<DryRunSpi<4>>>();
+    assert_send::<Bd18378<'static, DryRunSpi<4>>>();
+    assert_send::<Bd18378Owned<DryRunSpi<4>, NoInterlock, fn(WriteRegister, u8)>>();
+}
@@ -0,0 +1,418 @@
+use bd18378::Bd18378;
+use bd18378::registers::{ReadRegister, WriteRegister};
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiDevice};
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+/// A SPI device failing every transaction with an overrun.
+struct FailingSpi;
+
+impl ErrorType for FailingSpi {
+    type Error = ErrorKind;
+}
+
+impl SpiDevice for FailingSpi {
+    fn transaction(&mut self, _operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        Err(ErrorKind::Overrun)
+    }
+}
+
+#[test]
+fn register_read_success() {
+
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, 0b0000_1001u8],
+        ),
+        Transaction::transaction_end(),
+    ];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.read_register(ReadRegister::Status);
+    assert_eq!(result, Ok(0b0000_1001u8));
+
+    spi.done();
+}
+
+#[test]
+fn register_read_unexpected_response() {
+
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![0x00, 0b0000_1001u8],
+        ),
+        Transaction::transaction_end(),
+    ];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.read_register(ReadRegister::Status);
+    assert_eq!(
+        result,
+        Err(bd18378::Error::CommunicationError {
+            expected: [ReadRegister::Status as u8, 0x00u8],
+            received: [0x00, 0b0000_1001u8],
+        })
+    );
+
+    spi.done();
+}
+
+#[test]
+fn register_read_spi_error() {
+
+    let mut spi = FailingSpi;
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.read_register(ReadRegister::Status);
+    assert_eq!(result, Err(bd18378::Error::SpiError(ErrorKind::Overrun)));
+}
+
+#[test]
+fn register_write_raw_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.write_raw(WriteRegister::ReservedB5, 0x12u8);
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn register_write_raw_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let raw_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ReservedB5 as u8, 0x12u8],
+            vec![0xAB, 0xCD],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut raw_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.write_raw(WriteRegister::ReservedB5, 0x12u8);
+    assert_eq!(result, Ok([0xAB, 0xCD]));
+
+    spi.done();
+}
+
+#[test]
+fn register_write_raw_invalidates_cache() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let raw_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration02 as u8, 0x10u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration02 as u8, 0x20u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration02 as u8, 0x10u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut raw_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.set_channel_calibration(2, 0x10u8).unwrap();
+    bd18378.write_raw(WriteRegister::ChannelCalibration02, 0x20u8).unwrap();
+    bd18378.set_channel_calibration(2, 0x10u8).unwrap();
+    assert_eq!(bd18378.get_channel_calibration(2), Ok(Some(0x10u8)));
+
+    spi.done();
+}
+
+#[test]
+fn register_write_raw_invalidates_enable_cache() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let raw_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable00To05 as u8, 0b0000_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable06To11 as u8, 0x00u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable00To05 as u8, 0x00u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable00To05 as u8, 0b0000_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut raw_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.write_channel_mask(0b0000_0000_0000_0001u16).unwrap();
+    bd18378.write_raw(WriteRegister::ChannelEnable00To05, 0x00u8).unwrap();
+    // only the register written raw is written again
+    bd18378.update_all_channels().unwrap();
+
+    spi.done();
+}
+
+#[test]
+fn register_write_raw_software_reset() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let raw_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration02 as u8, 0x10u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::SoftwareReset as u8, 0b1010_0001u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut raw_expectations.to_vec());
+    expectations.extend(common::get_init_sequence_spi_expectations());
+    expectations.extend([
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration02 as u8, 0x10u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ]);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.set_channel_calibration(2, 0x10u8).unwrap();
+    bd18378.write_raw(WriteRegister::SoftwareReset, 0b1010_0001u8).unwrap();
+
+    assert!(!bd18378.is_initialized());
+    assert_eq!(bd18378.get_channel_calibration(2), Ok(None));
+
+    bd18378.init().unwrap();
+    bd18378.set_channel_calibration(2, 0x10u8).unwrap();
+
+    spi.done();
+}
+
+#[test]
+fn register_addresses() {
+
+    assert_eq!(WriteRegister::StatusReset.address(), 0x6B);
+    assert_eq!(WriteRegister::ChannelCalibration00.address(), 0x48);
+    assert_eq!(ReadRegister::Status.address(), 0xA8);
+}
+
+#[test]
+fn register_equality() {
+
+    let captured = WriteRegister::try_from(0x56u8).unwrap();
+
+    assert_eq!(captured, WriteRegister::ChannelEnable00To05);
+    assert_ne!(captured, WriteRegister::ChannelEnable06To11);
+    assert_eq!(ReadRegister::Status, ReadRegister::Status);
+    assert_ne!(ReadRegister::Status, ReadRegister::OpenFault00To05);
+
+    let registers: std::collections::HashSet<WriteRegister> =
+        [WriteRegister::StatusReset, WriteRegister::StatusReset].into_iter().collect();
+    assert_eq!(registers.len(), 1);
+}
+
+#[test]
+fn read_register_try_from() {
+
+    assert_eq!(ReadRegister::try_from(0xA8u8), Ok(ReadRegister::Status));
+    assert_eq!(ReadRegister::try_from(0xACu8), Ok(ReadRegister::ShortFault00To05));
+    assert_eq!(ReadRegister::try_from(0xAFu8), Ok(ReadRegister::OpenFault06To11));
+    assert_eq!(ReadRegister::try_from(0x6Bu8), Err(()));
+    assert_eq!(ReadRegister::try_from(0x00u8), Err(()));
+}
+
+#[test]
+fn last_transfer_response() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let write_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration05 as u8, 0x11u8],
+            vec![WriteRegister::StatusReset as u8, 0b0011_1111u8],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut write_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    assert_eq!(bd18378.last_transfer_response(), [0x00, 0x00]);
+    bd18378.init().unwrap();
+    bd18378.set_channel_calibration(5, 0x11u8).unwrap();
+
+    assert_eq!(
+        bd18378.last_transfer_response(),
+        [WriteRegister::StatusReset as u8, 0b0011_1111u8]
+    );
+
+    spi.done();
+}
+
+#[test]
+fn write_count_only_counts_real_writes() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let write_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration02 as u8, 0x20u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable00To05 as u8, 0b0000_0100u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable06To11 as u8, 0b0000_0000u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut write_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    assert_eq!(bd18378.write_count(), 0);
+    bd18378.init().unwrap();
+    // 15 frames of the init sequence and the status reset
+    assert_eq!(bd18378.write_count(), 16);
+
+    bd18378.set_channel_calibration(2, 0x20u8).unwrap();
+    assert_eq!(bd18378.write_count(), 17);
+    bd18378.set_channel_calibration(2, 0x20u8).unwrap();
+    assert_eq!(bd18378.write_count(), 17);
+
+    bd18378.enable_channel(2).unwrap();
+    bd18378.update_all_channels().unwrap();
+    assert_eq!(bd18378.write_count(), 19);
+    bd18378.update_all_channels().unwrap();
+    assert_eq!(bd18378.write_count(), 19);
+
+    spi.done();
+}
+
+#[test]
+fn write_observer_captures_channel_update() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let update_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable00To05 as u8, 0b0000_0100u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable06To11 as u8, 0b0000_0010u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut update_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut captured = Vec::new();
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let mut bd18378 = bd18378
+        .with_write_observer(|register: WriteRegister, value: u8| captured.push((register, value)));
+    bd18378.enable_channel(2).unwrap();
+    bd18378.enable_channel(7).unwrap();
+    bd18378.update_all_channels().unwrap();
+
+    assert_eq!(
+        captured,
+        [
+            (WriteRegister::ChannelEnable00To05, 0b0000_0100u8),
+            (WriteRegister::ChannelEnable06To11, 0b0000_0010u8),
+        ]
+    );
+
+    spi.done();
+}
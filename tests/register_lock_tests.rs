@@ -0,0 +1,272 @@
+use bd18378::Bd18378;
+use bd18378::registers::{ReadRegister, WriteRegister};
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+/// Returns the expectations for a single register write answered with `response`.
+fn write(register: WriteRegister, value: u8, response: [u8; 2]) -> [Transaction<u8>; 3] {
+    [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(vec![register as u8, value], response.to_vec()),
+        Transaction::transaction_end(),
+    ]
+}
+
+#[test]
+fn lock_registers_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+
+    assert_eq!(bd18378.lock_registers(), Err(bd18378::Error::NotInitialized));
+    assert_eq!(bd18378.unlock_registers(), Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn lock_registers_success() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(write(
+        WriteRegister::WriteLock,
+        0b0001_1111u8,
+        [ReadRegister::Status as u8, 0x00u8],
+    ));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.lock_registers();
+
+    assert_eq!(result, Ok(()));
+
+    spi.done();
+}
+
+#[test]
+fn unlock_registers_success() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(write(
+        WriteRegister::WriteLock,
+        0b0001_1111u8,
+        [ReadRegister::Status as u8, 0x00u8],
+    ));
+    expectations.extend(write(
+        WriteRegister::WriteUnlock,
+        0b0001_1111u8,
+        [WriteRegister::WriteLock as u8, 0b0001_1111u8],
+    ));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.lock_registers().unwrap();
+    let result = bd18378.unlock_registers();
+
+    assert_eq!(result, Ok(()));
+
+    spi.done();
+}
+
+#[test]
+fn locked_write_auto_unlock() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(write(
+        WriteRegister::WriteLock,
+        0b0001_1111u8,
+        [ReadRegister::Status as u8, 0x00u8],
+    ));
+    expectations.extend(write(
+        WriteRegister::WriteUnlock,
+        0b0001_1111u8,
+        [WriteRegister::WriteLock as u8, 0b0001_1111u8],
+    ));
+    expectations.extend(write(
+        WriteRegister::ChannelCalibration01,
+        0x0Au8,
+        [WriteRegister::WriteUnlock as u8, 0b0001_1111u8],
+    ));
+    expectations.extend(write(
+        WriteRegister::WriteLock,
+        0b0001_1111u8,
+        [WriteRegister::ChannelCalibration01 as u8, 0x0Au8],
+    ));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.lock_registers().unwrap();
+    bd18378.set_auto_unlock(true);
+    let result = bd18378.set_channel_calibration(1, 0x0Au8);
+
+    assert_eq!(result, Ok(()));
+
+    spi.done();
+}
+
+#[test]
+fn locked_update_all_channels_unlocks_once() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(write(
+        WriteRegister::WriteLock,
+        0b0001_1111u8,
+        [ReadRegister::Status as u8, 0x00u8],
+    ));
+    expectations.extend(write(
+        WriteRegister::WriteUnlock,
+        0b0001_1111u8,
+        [WriteRegister::WriteLock as u8, 0b0001_1111u8],
+    ));
+    expectations.extend(write(
+        WriteRegister::ChannelEnable00To05,
+        0b0000_0001u8,
+        [WriteRegister::WriteUnlock as u8, 0b0001_1111u8],
+    ));
+    expectations.extend(write(
+        WriteRegister::ChannelEnable06To11,
+        0b0000_0001u8,
+        [WriteRegister::ChannelEnable00To05 as u8, 0b0000_0001u8],
+    ));
+    expectations.extend(write(
+        WriteRegister::WriteLock,
+        0b0001_1111u8,
+        [WriteRegister::ChannelEnable06To11 as u8, 0b0000_0001u8],
+    ));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.set_auto_unlock(true);
+    bd18378.lock_registers().unwrap();
+    bd18378.set_channels(0b0000_0000_0100_0001u16).unwrap();
+    let result = bd18378.update_all_channels();
+
+    assert_eq!(result, Ok(()));
+
+    spi.done();
+}
+
+#[test]
+fn locked_write_without_auto_unlock() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(write(
+        WriteRegister::WriteLock,
+        0b0001_1111u8,
+        [ReadRegister::Status as u8, 0x00u8],
+    ));
+    expectations.extend(write(
+        WriteRegister::ChannelCalibration01,
+        0x0Au8,
+        [WriteRegister::WriteLock as u8, 0b0001_1111u8],
+    ));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.lock_registers().unwrap();
+    let result = bd18378.set_channel_calibration(1, 0x0Au8);
+
+    assert_eq!(result, Ok(()));
+
+    spi.done();
+}
+
+#[test]
+fn init_clears_register_lock() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(write(
+        WriteRegister::WriteLock,
+        0b0001_1111u8,
+        [ReadRegister::Status as u8, 0x00u8],
+    ));
+    expectations.extend(common::get_init_sequence_spi_expectations());
+    expectations.extend(write(
+        WriteRegister::ChannelCalibration01,
+        0x0Au8,
+        [ReadRegister::Status as u8, 0x00u8],
+    ));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.set_auto_unlock(true);
+    bd18378.init().unwrap();
+    bd18378.lock_registers().unwrap();
+    // the initialization sequence resets the IC, which unlocks all registers
+    bd18378.init().unwrap();
+    let result = bd18378.set_channel_calibration(1, 0x0Au8);
+
+    assert_eq!(result, Ok(()));
+
+    spi.done();
+}
+
+/// Returns the expectations for reading the unlock state register of an initialized
+/// driver, answered with `value`.
+fn unlock_state_read(value: u8) -> [Transaction<u8>; 6] {
+    [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::UnlockState as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, 0x00u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::UnlockState as u8, value],
+        ),
+        Transaction::transaction_end(),
+    ]
+}
+
+#[test]
+fn is_locked_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+
+    assert_eq!(bd18378.is_locked(), Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn is_locked_all_unlocked() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(unlock_state_read(0b0001_1111u8));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+
+    assert_eq!(bd18378.is_locked(), Ok(false));
+
+    spi.done();
+}
+
+#[test]
+fn is_locked_calibration_locked() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(unlock_state_read(0b0001_1101u8));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+
+    assert_eq!(bd18378.is_locked(), Ok(true));
+
+    spi.done();
+}
@@ -0,0 +1,180 @@
+use bd18378::Bd18378;
+use bd18378::registers::{ReadRegister, WriteRegister};
+use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+/// Appends the expectations for writing the channel enable registers, skipping the
+/// registers already holding the new value.
+fn push_group_writes(
+    expectations: &mut Vec<Transaction<u8>>,
+    written: &mut [Option<u8>; 2],
+    values: [u8; 2],
+) {
+    let registers = [
+        WriteRegister::ChannelEnable00To05,
+        WriteRegister::ChannelEnable06To11,
+    ];
+    let changed: Vec<usize> = (0..2).filter(|g| written[*g] != Some(values[*g])).collect();
+    for group in changed {
+        expectations.push(Transaction::transaction_start());
+        expectations.push(Transaction::transfer_in_place(
+            vec![registers[group] as u8, values[group]],
+            vec![0x00, 0x00],
+        ));
+        expectations.push(Transaction::transaction_end());
+    }
+    *written = [Some(values[0]), Some(values[1])];
+}
+
+/// Appends the expectations for reading `register`, answered with `value`.
+fn push_read(expectations: &mut Vec<Transaction<u8>>, register: ReadRegister, value: u8) {
+    expectations.push(Transaction::transaction_start());
+    expectations.push(Transaction::transfer_in_place(
+        vec![register as u8, 0x00u8],
+        vec![0x00, 0x00],
+    ));
+    expectations.push(Transaction::transaction_end());
+    expectations.push(Transaction::transaction_start());
+    expectations.push(Transaction::transfer_in_place(
+        vec![ReadRegister::Status as u8, 0x00u8],
+        vec![register as u8, value],
+    ));
+    expectations.push(Transaction::transaction_end());
+}
+
+/// Expectations for a self-test stopping after the channel `last`, if any. `report`
+/// returns the open mask, the short mask and the status read while a channel is enabled.
+fn get_self_test_expectations(
+    last: Option<usize>,
+    report: impl Fn(usize) -> (u16, u16, u8),
+) -> (Vec<Transaction<u8>>, Vec<DelayTransaction>) {
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    let mut delays = Vec::new();
+    let mut written = [None, None];
+    for channel in 0..=last.unwrap_or(11) {
+        expectations.push(Transaction::transaction_start());
+        expectations.push(Transaction::transfer_in_place(
+            vec![WriteRegister::StatusReset as u8, 0b0011_1111u8],
+            vec![0x00, 0x00],
+        ));
+        expectations.push(Transaction::transaction_end());
+
+        let mask = 1u16 << channel;
+        let values = [(mask & 0x3F) as u8, (mask >> 6) as u8];
+        push_group_writes(&mut expectations, &mut written, values);
+        delays.push(DelayTransaction::delay_us(1_000));
+
+        let (open, short, status) = report(channel);
+        push_read(&mut expectations, ReadRegister::ShortFault00To05, (short & 0x3F) as u8);
+        push_read(&mut expectations, ReadRegister::ShortFault06To11, (short >> 6) as u8);
+        push_read(&mut expectations, ReadRegister::OpenFault00To05, (open & 0x3F) as u8);
+        push_read(&mut expectations, ReadRegister::OpenFault06To11, (open >> 6) as u8);
+        push_read(&mut expectations, ReadRegister::Status, status);
+    }
+    push_group_writes(&mut expectations, &mut written, [0x00, 0x00]);
+    (expectations, delays)
+}
+
+#[test]
+fn self_test_success() {
+
+    let (expectations, delays) = get_self_test_expectations(None, |_| (0, 0, 0x00));
+    let mut spi = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&delays);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.self_test(&mut delay);
+
+    assert_eq!(result, Ok(()));
+    assert!(bd18378.is_initialized());
+    assert_eq!(bd18378.enabled_channels_mask(), 0);
+
+    spi.done();
+    delay.done();
+}
+
+#[test]
+fn self_test_open() {
+
+    let (expectations, delays) = get_self_test_expectations(Some(7), |channel| {
+        if channel == 7 {
+            (1 << 7, 0, 0b0010_0000u8)
+        } else {
+            (0, 0, 0x00)
+        }
+    });
+    let mut spi = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&delays);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.self_test(&mut delay);
+
+    assert_eq!(result, Err(bd18378::Error::ChannelFault { channel: 7 }));
+    assert_eq!(bd18378.enabled_channels_mask(), 0);
+
+    spi.done();
+    delay.done();
+}
+
+#[test]
+fn self_test_short() {
+
+    let (expectations, delays) = get_self_test_expectations(Some(2), |channel| {
+        if channel == 2 {
+            (0, 1 << 2, 0b0010_0000u8)
+        } else {
+            (0, 0, 0x00)
+        }
+    });
+    let mut spi = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&delays);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.self_test(&mut delay);
+
+    assert_eq!(result, Err(bd18378::Error::ChannelFault { channel: 2 }));
+
+    spi.done();
+    delay.done();
+}
+
+#[test]
+fn self_test_short_to_ground() {
+
+    let (expectations, delays) = get_self_test_expectations(Some(0), |_| (0, 0, 0b0100_0000u8));
+    let mut spi = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&delays);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.self_test(&mut delay);
+
+    assert_eq!(result, Err(bd18378::Error::ChannelFault { channel: 0 }));
+
+    spi.done();
+    delay.done();
+}
+
+#[test]
+fn self_test_fault_not_reported_for_next_channel() {
+
+    // the open detected on channel 3 is still set while channel 4 is tested
+    let (expectations, delays) = get_self_test_expectations(None, |channel| {
+        if channel == 4 {
+            (1 << 3, 0, 0b0010_0000u8)
+        } else {
+            (0, 0, 0x00)
+        }
+    });
+    let mut spi = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&delays);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.self_test(&mut delay);
+
+    assert_eq!(result, Ok(()));
+
+    spi.done();
+    delay.done();
+}
@@ -0,0 +1,110 @@
+use bd18378::Bd18378;
+use bd18378::registers::WriteRegister;
+use bd18378::state::DriverState;
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+#[test]
+fn dump_state_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let bd18378 = Bd18378::new(&mut spi);
+
+    assert_eq!(
+        bd18378.dump_state(),
+        DriverState {
+            initialized: false,
+            channel_mask: 0,
+            calibration: [None; 12],
+        }
+    );
+
+    spi.done();
+}
+
+#[test]
+fn dump_state_reflects_cached_state() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let calibration_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration04 as u8, 0x21u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut calibration_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(4).unwrap();
+    bd18378.enable_channel(9).unwrap();
+    bd18378.set_channel_calibration(4, 0x21u8).unwrap();
+
+    let mut calibration = [None; 12];
+    calibration[4] = Some(0x21u8);
+    assert_eq!(
+        bd18378.dump_state(),
+        DriverState {
+            initialized: true,
+            channel_mask: 0b0010_0001_0000,
+            calibration,
+        }
+    );
+
+    spi.done();
+}
+
+#[test]
+fn debug_output() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    assert_eq!(
+        format!("{:?}", bd18378),
+        "Bd18378 { is_initialized: false, channel_mask: 0b000000000000, .. }"
+    );
+    bd18378.init().unwrap();
+    bd18378.enable_channel(0).unwrap();
+    bd18378.enable_channel(11).unwrap();
+
+    let output = format!("{:?}", bd18378);
+    assert!(output.contains("is_initialized: true"));
+    assert!(output.contains("channel_mask: 0b100000000001"));
+
+    spi.done();
+}
+
+#[test]
+fn dump_state_snapshot_diff() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+    let mut spi = Mock::new(&init_expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(2).unwrap();
+
+    let before = bd18378.dump_state();
+    let copy = before;
+    bd18378.enable_channel(5).unwrap();
+    bd18378.disable_channel(2).unwrap();
+    let after = bd18378.dump_state();
+
+    assert_eq!(before, copy);
+    assert_ne!(before, after);
+    assert_eq!(before.channel_mask ^ after.channel_mask, 0b0000_0010_0100);
+    assert_eq!(before.calibration, after.calibration);
+
+    spi.done();
+}
@@ -0,0 +1,652 @@
+use bd18378::Bd18378;
+use bd18378::registers::{ReadRegister, WriteRegister};
+use bd18378::status::{ChannelFaults, Status};
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiDevice};
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+#[test]
+fn status_read_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.read_status();
+
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn status_read_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let status_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, 0x00u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, 0b0010_0100u8],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut status_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.read_status();
+    assert_eq!(result, Ok(0b0010_0100u8));
+
+    spi.done();
+}
+
+/// A SPI device emulating the echo protocol of the IC, failing all reads of the status
+/// register except for the one at the end of the initialization sequence.
+struct StatusFailingSpi {
+    next_response: [u8; 2],
+    status_frames: usize,
+}
+
+impl ErrorType for StatusFailingSpi {
+    type Error = ErrorKind;
+}
+
+impl SpiDevice for StatusFailingSpi {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        for operation in operations.iter_mut() {
+            if let Operation::TransferInPlace(data) = operation {
+                let frame = [data[0], data[1]];
+                data.copy_from_slice(&self.next_response);
+                self.next_response = frame;
+                if frame[0] == ReadRegister::Status as u8 {
+                    // the status read of the initialization takes two frames
+                    self.status_frames += 1;
+                    if self.status_frames > 2 {
+                        return Err(ErrorKind::Other);
+                    }
+                    self.next_response = [frame[0], 0x00];
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn status_read_spi_error() {
+
+    let mut spi = StatusFailingSpi {
+        next_response: [0x00, 0x00],
+        status_frames: 0,
+    };
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.read_status();
+    assert_eq!(result, Err(bd18378::Error::SpiError(ErrorKind::Other)));
+}
+
+#[test]
+fn status_decode_flags() {
+    let status = Status::from_bits(0b0000_0000);
+    assert!(!status.power_on_reset());
+    assert!(!status.over_temperature());
+    assert!(!status.has_fault());
+
+    let status = Status::from_bits(0b0000_0001);
+    assert!(status.power_on_reset());
+    assert!(!status.has_fault());
+
+    let status = Status::from_bits(0b0000_0010);
+    assert!(status.temperature_warning());
+    assert!(!status.thermal_shutdown());
+    assert!(status.over_temperature());
+
+    let status = Status::from_bits(0b0000_0100);
+    assert!(status.thermal_shutdown());
+    assert!(!status.temperature_warning());
+    assert!(status.over_temperature());
+
+    let status = Status::from_bits(0b0000_1000);
+    assert!(status.weak_led_supply());
+    assert!(status.has_fault());
+
+    let status = Status::from_bits(0b0001_0000);
+    assert!(status.rext_fault());
+
+    let status = Status::from_bits(0b0010_0000);
+    assert!(status.short_or_open());
+    assert!(!status.short_to_ground());
+
+    let status = Status::from_bits(0b0100_0000);
+    assert!(status.short_to_ground());
+    assert!(!status.short_or_open());
+
+    // bit 7 is unused
+    let status = Status::from_bits(0b1000_0000);
+    assert!(!status.has_fault());
+    assert_eq!(status.bits(), 0b1000_0000);
+}
+
+#[test]
+fn status_read_flags_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let status_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, 0x00u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, 0b0000_0101u8],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut status_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let status = bd18378.read_status_flags().unwrap();
+    assert!(status.power_on_reset());
+    assert!(status.thermal_shutdown());
+    assert!(!status.temperature_warning());
+    assert!(!status.short_or_open());
+
+    spi.done();
+}
+
+fn fault_read(register: ReadRegister, value: u8) -> [Transaction<u8>; 6] {
+    [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![register as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, 0x00u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![register as u8, value],
+        ),
+        Transaction::transaction_end(),
+    ]
+}
+
+#[test]
+fn channel_faults_read() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.extend(fault_read(ReadRegister::ShortFault00To05, 0b1100_0000u8));
+    expectations.extend(fault_read(ReadRegister::ShortFault06To11, 0b0000_0010u8));
+    expectations.extend(fault_read(ReadRegister::OpenFault00To05, 0b0000_0101u8));
+    expectations.extend(fault_read(ReadRegister::OpenFault06To11, 0b0010_0000u8));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let faults = bd18378.read_channel_faults().unwrap();
+
+    // Bits beyond the 6 channels of a group are ignored.
+    assert_eq!(
+        faults,
+        ChannelFaults {
+            open: 0b1000_0000_0101,
+            short: 0b0000_1000_0000,
+        }
+    );
+    assert!(faults.any());
+
+    spi.done();
+}
+
+#[test]
+fn channel_faults_none() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.extend(fault_read(ReadRegister::ShortFault00To05, 0x00));
+    expectations.extend(fault_read(ReadRegister::ShortFault06To11, 0x00));
+    expectations.extend(fault_read(ReadRegister::OpenFault00To05, 0x00));
+    expectations.extend(fault_read(ReadRegister::OpenFault06To11, 0x00));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let faults = bd18378.read_channel_faults().unwrap();
+
+    assert_eq!(faults, ChannelFaults::default());
+    assert!(!faults.any());
+
+    spi.done();
+}
+
+#[test]
+fn channel_faults_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.read_channel_faults();
+
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn clear_faults_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let clear_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::StatusReset as u8, 0b0011_1111u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut clear_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.clear_faults();
+
+    assert_eq!(result, Ok(()));
+
+    spi.done();
+}
+
+#[test]
+fn clear_faults_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.clear_faults();
+
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+fn status_read(value: u8) -> [Transaction<u8>; 6] {
+    fault_read(ReadRegister::Status, value)
+}
+
+#[test]
+fn refresh_initialized_state_configured() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(status_read(0b0000_0010u8));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+
+    assert_eq!(bd18378.refresh_initialized_state(), Ok(true));
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn refresh_initialized_state_after_power_on_reset() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(status_read(0b0000_0001u8));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+
+    assert_eq!(bd18378.refresh_initialized_state(), Ok(false));
+    assert!(!bd18378.is_initialized());
+    assert_eq!(bd18378.enable_channel(0), Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn refresh_initialized_state_unexpected_response() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend([
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, 0x00u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![0xFF, 0xFF],
+        ),
+        Transaction::transaction_end(),
+    ]);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+
+    assert!(matches!(
+        bd18378.refresh_initialized_state(),
+        Err(bd18378::Error::CommunicationError { .. })
+    ));
+    assert!(bd18378.is_initialized());
+
+    spi.done();
+}
+
+#[test]
+fn refresh_initialized_state_no_init() {
+
+    let expectations = status_read(0x00);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+
+    assert_eq!(bd18378.refresh_initialized_state(), Ok(false));
+
+    spi.done();
+}
+
+#[test]
+fn read_and_clear_status_success() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(status_read(0b0010_0000u8));
+    expectations.extend([
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::StatusReset as u8, 0b0011_1111u8],
+            vec![ReadRegister::Status as u8, 0b0010_0000u8],
+        ),
+        Transaction::transaction_end(),
+    ]);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let write_count = bd18378.write_count();
+    let result = bd18378.read_and_clear_status();
+
+    assert_eq!(result, Ok(0b0010_0000u8));
+    assert_eq!(bd18378.write_count(), write_count + 1);
+    assert_eq!(
+        bd18378.last_transfer_response(),
+        [ReadRegister::Status as u8, 0b0010_0000u8]
+    );
+
+    spi.done();
+}
+
+#[test]
+fn read_and_clear_status_auto_status_check() {
+
+    // the status reset is checked like any other write
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(status_read(0b0000_0001u8));
+    expectations.extend([
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::StatusReset as u8, 0b0011_1111u8],
+            vec![ReadRegister::Status as u8, 0b0000_0001u8],
+        ),
+        Transaction::transaction_end(),
+    ]);
+    expectations.extend(status_read(0x00));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.set_auto_status_check(true);
+    bd18378.init().unwrap();
+    let result = bd18378.read_and_clear_status();
+
+    assert_eq!(result, Ok(0b0000_0001u8));
+
+    spi.done();
+}
+
+#[test]
+fn read_and_clear_status_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.read_and_clear_status();
+
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+/// Expectations for a write followed by the automatic status check, each frame answered
+/// like the IC does after the initialization, which ends with a status read.
+fn checked_write(register: WriteRegister, value: u8, status: u8) -> [Transaction<u8>; 9] {
+    [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![register as u8, value],
+            vec![ReadRegister::Status as u8, 0x00u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![register as u8, value],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, status],
+        ),
+        Transaction::transaction_end(),
+    ]
+}
+
+#[test]
+fn auto_status_check_clean_write() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(checked_write(WriteRegister::ChannelCalibration01, 0x0Au8, 0x00));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.set_auto_status_check(true);
+    bd18378.init().unwrap();
+    let result = bd18378.set_channel_calibration(1, 0x0Au8);
+
+    assert_eq!(result, Ok(()));
+
+    spi.done();
+}
+
+/// Returns the expectations for writing both channel enable registers of an initialized
+/// driver, followed by the automatic status check reading `status`.
+fn checked_group_writes(values: [u8; 2], status: u8) -> [Transaction<u8>; 12] {
+    [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable00To05 as u8, values[0]],
+            vec![ReadRegister::Status as u8, 0x00u8],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelEnable06To11 as u8, values[1]],
+            vec![WriteRegister::ChannelEnable00To05 as u8, values[0]],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![WriteRegister::ChannelEnable06To11 as u8, values[1]],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![ReadRegister::Status as u8, 0x00u8],
+            vec![ReadRegister::Status as u8, status],
+        ),
+        Transaction::transaction_end(),
+    ]
+}
+
+#[test]
+fn auto_status_check_reports_fault() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(checked_group_writes([0b0000_0001u8, 0x00u8], 0b0010_0000u8));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.set_auto_status_check(true);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(0).unwrap();
+    let result = bd18378.update_all_channels();
+
+    assert_eq!(result, Err(bd18378::Error::Fault { status: 0b0010_0000u8 }));
+
+    spi.done();
+}
+
+#[test]
+fn auto_status_check_after_all_writes() {
+
+    // the fault flag is already latched when the first channel enable register is written
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(checked_group_writes([0b0000_0010u8, 0b0000_0100u8], 0b0100_0000u8));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.set_auto_status_check(true);
+    bd18378.init().unwrap();
+    bd18378.set_channels(0b0000_0001_0000_0010u16).unwrap();
+    let result = bd18378.update_all_channels();
+
+    assert_eq!(result, Err(bd18378::Error::Fault { status: 0b0100_0000u8 }));
+
+    // both registers have been written and cached, so nothing is written or checked again
+    assert_eq!(bd18378.update_all_channels(), Ok(()));
+
+    spi.done();
+}
+
+#[test]
+fn auto_status_check_reports_weak_led_supply() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(checked_write(WriteRegister::ChannelCalibration01, 0x0Au8, 0b0000_1000u8));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.set_auto_status_check(true);
+    bd18378.init().unwrap();
+    let result = bd18378.set_channel_calibration(1, 0x0Au8);
+
+    assert_eq!(result, Err(bd18378::Error::Fault { status: 0b0000_1000u8 }));
+
+    spi.done();
+}
+
+#[test]
+fn auto_status_check_reports_reset() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(checked_write(WriteRegister::ChannelCalibration01, 0x0Au8, 0b0000_0001u8));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.set_auto_status_check(true);
+    bd18378.init().unwrap();
+    let result = bd18378.set_channel_calibration(1, 0x0Au8);
+
+    assert_eq!(result, Err(bd18378::Error::UnexpectedReset));
+
+    spi.done();
+}
+
+#[test]
+fn auto_status_check_reset_discards_cached_state() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend(checked_write(WriteRegister::ChannelCalibration01, 0x0Au8, 0b0000_0001u8));
+    expectations.extend(common::get_init_sequence_spi_expectations());
+    expectations.extend(checked_write(WriteRegister::ChannelCalibration01, 0x0Au8, 0x00));
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.set_auto_status_check(true);
+    bd18378.init().unwrap();
+    let result = bd18378.set_channel_calibration(1, 0x0Au8);
+
+    assert_eq!(result, Err(bd18378::Error::UnexpectedReset));
+    assert!(!bd18378.is_initialized());
+    assert_eq!(
+        bd18378.set_channel_calibration(1, 0x0Au8),
+        Err(bd18378::Error::NotInitialized)
+    );
+
+    // the same value is written again, as the device has lost it
+    bd18378.init().unwrap();
+    let result = bd18378.set_channel_calibration(1, 0x0Au8);
+
+    assert_eq!(result, Ok(()));
+
+    spi.done();
+}
+
+#[test]
+fn auto_status_check_disabled_by_default() {
+
+    let mut expectations = common::get_init_sequence_spi_expectations().to_vec();
+    expectations.extend([
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![WriteRegister::ChannelCalibration01 as u8, 0x0Au8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ]);
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    let result = bd18378.set_channel_calibration(1, 0x0Au8);
+
+    assert_eq!(result, Ok(()));
+
+    spi.done();
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "std")]
+
+use bd18378::Error;
+use embedded_hal::spi::ErrorKind;
+
+#[test]
+fn error_into_io_error() {
+
+    let error: std::io::Error = Error::NotInitialized.into();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::Other);
+    assert_eq!(error.to_string(), "device not initialized");
+    assert_eq!(
+        error.into_inner().unwrap().downcast_ref::<Error>(),
+        Some(&Error::NotInitialized)
+    );
+}
+
+#[test]
+fn error_as_boxed_std_error() {
+
+    fn fails() -> Result<(), Box<dyn std::error::Error>> {
+        Err(Error::SpiError(ErrorKind::Other))?;
+        Ok(())
+    }
+
+    let error = fails().unwrap_err();
+
+    assert_eq!(error.to_string(), "SPI bus error");
+    assert_eq!(error.downcast_ref::<Error>(), Some(&Error::SpiError(ErrorKind::Other)));
+}
@@ -0,0 +1,68 @@
+use bd18378::Bd18378;
+use bd18378::pattern::TestPattern;
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+#[test]
+fn test_pattern_masks() {
+    assert_eq!(TestPattern::AllOn.mask(), Ok(0b1111_1111_1111));
+    assert_eq!(TestPattern::AllOff.mask(), Ok(0b0000_0000_0000));
+    assert_eq!(TestPattern::EvenOdd.mask(), Ok(0b0101_0101_0101));
+    assert_eq!(TestPattern::Checkerboard.mask(), Ok(0b1010_1001_0101));
+    assert_eq!(TestPattern::Bars.mask(), Ok(0b0011_0011_0011));
+    for ch in 0..12 {
+        assert_eq!(TestPattern::WalkingOne(ch).mask(), Ok(1 << ch));
+    }
+    assert_eq!(
+        TestPattern::WalkingOne(12).mask(),
+        Err(bd18378::Error::InvalidChannel)
+    );
+}
+
+#[test]
+fn test_pattern_no_init() {
+
+    let expectations: [Transaction<u8>; 0] = [];
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    let result = bd18378.apply_test_pattern(TestPattern::AllOn);
+
+    assert_eq!(result, Err(bd18378::Error::NotInitialized));
+
+    spi.done();
+}
+
+#[test]
+fn test_pattern_checkerboard_success() {
+
+    let init_expectations = common::get_init_sequence_spi_expectations();
+
+    let pattern_expectations = [
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x56, 0b0001_0101u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::transfer_in_place(
+            vec![0x57, 0b0010_1010u8],
+            vec![0x00, 0x00],
+        ),
+        Transaction::transaction_end(),
+    ];
+
+    let mut expectations = init_expectations.to_vec();
+    expectations.append(&mut pattern_expectations.to_vec());
+    let mut spi = Mock::new(&expectations);
+
+    let mut bd18378 = Bd18378::new(&mut spi);
+    bd18378.init().unwrap();
+    bd18378.enable_channel(1).unwrap();
+    let result = bd18378.apply_test_pattern(TestPattern::Checkerboard);
+    assert!(result.is_ok());
+
+    spi.done();
+}